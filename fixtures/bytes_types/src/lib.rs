@@ -1,7 +1,3 @@
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
-
 #[uniffi::export]
 fn take_bytes(v: Vec<u8>) -> Vec<u8> {
     v
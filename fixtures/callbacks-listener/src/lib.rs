@@ -0,0 +1,26 @@
+use std::thread;
+
+trait ProgressListener: Send + Sync {
+    fn on_progress(&self, step: u32);
+    fn on_done(&self, label: String);
+}
+
+#[derive(Debug, Default)]
+pub struct Worker;
+
+impl Worker {
+    fn new() -> Self {
+        Worker
+    }
+
+    fn run_in_background(&self, listener: Box<dyn ProgressListener>, steps: u32) {
+        thread::spawn(move || {
+            for step in 1..=steps {
+                listener.on_progress(step);
+            }
+            listener.on_done(format!("finished {steps} steps"));
+        });
+    }
+}
+
+uniffi::include_scaffolding!("api");
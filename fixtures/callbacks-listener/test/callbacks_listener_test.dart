@@ -0,0 +1,30 @@
+import 'dart:async';
+
+import 'package:test/test.dart';
+import '../callbacks_listener.dart';
+
+class DartProgressListener extends ProgressListener {
+  final steps = <int>[];
+  final done = Completer<String>();
+
+  @override
+  void onProgress(int step) => steps.add(step);
+
+  @override
+  void onDone(String label) => done.complete(label);
+}
+
+void main() {
+  ensureInitialized();
+
+  final worker = Worker();
+
+  test('void callbacks invoked from a background thread', () async {
+    final listener = DartProgressListener();
+    worker.runInBackground(listener, 3);
+
+    final label = await listener.done.future.timeout(Duration(seconds: 5));
+    expect(label, equals('finished 3 steps'));
+    expect(listener.steps, equals([1, 2, 3]));
+  });
+}
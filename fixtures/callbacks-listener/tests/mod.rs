@@ -0,0 +1,26 @@
+use anyhow::Result;
+
+#[test]
+fn callbacks_listener() -> Result<()> {
+    uniffi_dart::testing::run_test("callbacks_listener", "src/api.udl", Some("uniffi.toml"))
+}
+
+#[test]
+fn value_returning_listeners_are_refused() {
+    let err = uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "../callbacks/src/api.udl".into(),
+        Some("uniffi.toml".into()),
+        Some("../../.tmp_tests/callbacks_listener_refused".into()),
+        None,
+        None,
+        false,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains(
+        "callbacks::ForeignGetters::get_nothing: listener callbacks can't report an error to Rust"
+    ));
+    assert!(err.contains(
+        "callbacks::StoredForeignStringifier::from_simple_type: listener callbacks can't return a value to Rust"
+    ));
+}
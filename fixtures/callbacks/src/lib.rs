@@ -1,4 +1,5 @@
-use uniffi;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 trait ForeignGetters {
     fn get_bool(&self, v: bool, argument_two: bool) -> Result<bool, SimpleError>;
@@ -103,6 +104,8 @@ impl Default for RustGetters {
 #[allow(clippy::wrong_self_convention)]
 trait StoredForeignStringifier: Send + Sync + std::fmt::Debug {
     fn from_simple_type(&self, value: i32) -> String;
+    // Only declared so the bindings collect its argument types.
+    #[allow(dead_code)]
     fn from_complex_type(&self, values: Option<Vec<Option<f64>>>) -> String;
 }
 
@@ -122,4 +125,184 @@ impl RustStringifier {
     }
 }
 
+trait ForeignNumbers: Send + Sync + std::fmt::Debug {
+    fn get_i8(&self, v: i8) -> i8;
+    fn get_u8(&self, v: u8) -> u8;
+    fn get_i16(&self, v: i16) -> i16;
+    fn get_u16(&self, v: u16) -> u16;
+    fn get_i32(&self, v: i32) -> i32;
+    fn get_u32(&self, v: u32) -> u32;
+    fn get_i64(&self, v: i64) -> i64;
+    fn get_u64(&self, v: u64) -> u64;
+    fn get_f32(&self, v: f32) -> f32;
+    fn get_f64(&self, v: f64) -> f64;
+    fn get_getters(&self) -> Arc<RustGetters>;
+}
+
+#[derive(Debug)]
+pub struct RustNumbers {
+    callback: Box<dyn ForeignNumbers>,
+}
+
+impl RustNumbers {
+    fn new(callback: Box<dyn ForeignNumbers>) -> Self {
+        RustNumbers { callback }
+    }
+
+    fn get_i8(&self, v: i8) -> i8 {
+        self.callback.get_i8(v)
+    }
+
+    fn get_u8(&self, v: u8) -> u8 {
+        self.callback.get_u8(v)
+    }
+
+    fn get_i16(&self, v: i16) -> i16 {
+        self.callback.get_i16(v)
+    }
+
+    fn get_u16(&self, v: u16) -> u16 {
+        self.callback.get_u16(v)
+    }
+
+    fn get_i32(&self, v: i32) -> i32 {
+        self.callback.get_i32(v)
+    }
+
+    fn get_u32(&self, v: u32) -> u32 {
+        self.callback.get_u32(v)
+    }
+
+    fn get_i64(&self, v: i64) -> i64 {
+        self.callback.get_i64(v)
+    }
+
+    fn get_u64(&self, v: u64) -> u64 {
+        self.callback.get_u64(v)
+    }
+
+    fn get_f32(&self, v: f32) -> f32 {
+        self.callback.get_f32(v)
+    }
+
+    fn get_f64(&self, v: f64) -> f64 {
+        self.callback.get_f64(v)
+    }
+
+    fn get_getters(&self) -> Arc<RustGetters> {
+        self.callback.get_getters()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+}
+
+trait ForeignShapes: Send + Sync + std::fmt::Debug {
+    fn check_getters(&self, getters: Arc<RustGetters>) -> bool;
+    fn move_point(&self, point: Point, direction: Direction) -> Point;
+    fn area(&self, shape: Shape) -> f64;
+    fn get_stringifier(&self) -> Box<dyn StoredForeignStringifier>;
+    fn stringify_with(&self, stringifier: Arc<RustStringifier>, value: i32) -> String;
+}
+
+#[derive(Debug)]
+pub struct RustShapes {
+    callback: Box<dyn ForeignShapes>,
+}
+
+impl RustShapes {
+    fn new(callback: Box<dyn ForeignShapes>) -> Self {
+        RustShapes { callback }
+    }
+
+    fn check_getters(&self) -> bool {
+        self.callback.check_getters(Arc::new(RustGetters::new()))
+    }
+
+    fn move_point(&self, point: Point, direction: Direction) -> Point {
+        self.callback.move_point(point, direction)
+    }
+
+    fn area(&self, shape: Shape) -> f64 {
+        self.callback.area(shape)
+    }
+
+    fn stringify(&self, value: i32) -> String {
+        self.callback.get_stringifier().from_simple_type(value)
+    }
+
+    fn stringify_with(&self, value: i32) -> String {
+        let stringifier = RustStringifier::new(self.callback.get_stringifier());
+        self.callback.stringify_with(Arc::new(stringifier), value)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RustStringifierStore {
+    callbacks: Mutex<Vec<Box<dyn StoredForeignStringifier>>>,
+}
+
+impl RustStringifierStore {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_optional(&self, callback: Option<Box<dyn StoredForeignStringifier>>) {
+        self.callbacks.lock().unwrap().extend(callback);
+    }
+
+    fn add_list(&self, callbacks: Vec<Box<dyn StoredForeignStringifier>>) {
+        self.callbacks.lock().unwrap().extend(callbacks);
+    }
+
+    fn add_map(&self, callbacks: HashMap<String, Box<dyn StoredForeignStringifier>>) {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .extend(callbacks.into_values());
+    }
+
+    fn add_nested(&self, callbacks: Option<Vec<Box<dyn StoredForeignStringifier>>>) {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .extend(callbacks.into_iter().flatten());
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    fn stringify_all(&self, value: i32) -> Vec<String> {
+        self.callbacks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|callback| callback.from_simple_type(value))
+            .collect()
+    }
+
+    fn count(&self) -> u64 {
+        self.callbacks.lock().unwrap().len() as u64
+    }
+
+    fn clear(&self) {
+        self.callbacks.lock().unwrap().clear();
+    }
+}
+
 uniffi::include_scaffolding!("api");
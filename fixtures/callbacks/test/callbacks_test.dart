@@ -1,5 +1,7 @@
 import 'package:test/test.dart';
 import '../callbacks.dart'; // Adjust import to your generated code and/or callback interfaces.
+import '../callbacks_ffi.dart'
+    show FfiConverterCallbackInterfaceStoredForeignStringifier;
 
 class DartGetters extends ForeignGetters {
   @override
@@ -51,15 +53,92 @@ class StoredDartStringifier extends StoredForeignStringifier {
   String fromComplexType(List<double?>? values) => 'kotlin: $values';
 }
 
+class DartNumbers extends ForeignNumbers {
+  @override
+  int getI8(int v) => v;
+
+  @override
+  int getU8(int v) => v;
+
+  @override
+  int getI16(int v) => v;
+
+  @override
+  int getU16(int v) => v;
+
+  @override
+  int getI32(int v) => v;
+
+  @override
+  int getU32(int v) => v;
+
+  @override
+  int getI64(int v) => v;
+
+  @override
+  int getU64(int v) => v;
+
+  @override
+  double getF32(double v) => v;
+
+  @override
+  double getF64(double v) => v;
+
+  @override
+  RustGetters getGetters() => RustGetters();
+}
+
+class DartShapes extends ForeignShapes {
+  final ForeignGetters getters;
+
+  DartShapes(this.getters);
+
+  @override
+  bool checkGetters(RustGetters rustGetters) =>
+      rustGetters.getBool(getters, true, false);
+
+  @override
+  Point movePoint(Point point, Direction direction) {
+    switch (direction) {
+      case Direction.north:
+        return Point(point.x, point.y + 1);
+      case Direction.east:
+        return Point(point.x + 1, point.y);
+      case Direction.south:
+        return Point(point.x, point.y - 1);
+      case Direction.west:
+        return Point(point.x - 1, point.y);
+    }
+  }
+
+  @override
+  double area(Shape shape) {
+    if (shape is CircleShape) {
+      return 3.0 * shape.radius * shape.radius;
+    }
+    if (shape is RectangleShape) {
+      return shape.width * shape.height;
+    }
+    throw ArgumentError('unknown shape $shape');
+  }
+
+  @override
+  StoredForeignStringifier getStringifier() => StoredDartStringifier();
+
+  @override
+  String stringifyWith(RustStringifier stringifier, int value) =>
+      stringifier.use((s) => s.fromSimpleType(value));
+}
+
 void main() {
+  // Registers every callback vtable as well
   ensureInitialized();
-  // Initialize all VTables
-  initForeignGettersVTable();
-  initStoredForeignStringifierVTable();
 
   final callback = DartGetters();
   final rustGetters = RustGetters();
   final rustStringifier = RustStringifier(StoredDartStringifier());
+  final rustNumbers = RustNumbers(DartNumbers());
+  final rustShapes = RustShapes(DartShapes(callback));
 
   test('roundtrip getBool through callback', () {
     final flag = true;
@@ -118,6 +197,73 @@ void main() {
     rustGetters.getNothing(callback, "1234567890123");
   });
 
+  test('primitive return values through callback', () {
+    expect(rustNumbers.getI8(-128), equals(-128));
+    expect(rustNumbers.getU8(255), equals(255));
+    expect(rustNumbers.getI16(-32768), equals(-32768));
+    expect(rustNumbers.getU16(65535), equals(65535));
+    expect(rustNumbers.getI32(-2147483648), equals(-2147483648));
+    expect(rustNumbers.getU32(4294967295), equals(4294967295));
+    expect(rustNumbers.getI64(-9007199254740991), equals(-9007199254740991));
+    expect(rustNumbers.getU64(9007199254740991), equals(9007199254740991));
+    expect(rustNumbers.getF32(1.5), equals(1.5));
+    expect(rustNumbers.getF64(3.14159), equals(3.14159));
+  });
+
+  test('object return value through callback', () {
+    final getters = rustNumbers.getGetters();
+    expect(getters.getBool(callback, true, false), isTrue);
+  });
+
+  test('object argument through callback', () {
+    expect(rustShapes.checkGetters(), isTrue);
+  });
+
+  test('record and flat enum arguments through callback', () {
+    final moved = rustShapes.movePoint(Point(1, 2), Direction.west);
+    expect(moved.x, equals(0));
+    expect(moved.y, equals(2));
+  });
+
+  test('data enum argument through callback', () {
+    expect(rustShapes.area(CircleShape(2.0)), equals(12.0));
+    expect(rustShapes.area(RectangleShape(width: 2.0, height: 3.5)),
+        equals(7.0));
+  });
+
+  test('callback interface returned from callback', () {
+    expect(rustShapes.stringify(42), equals('kotlin: 42'));
+  });
+
+  test('object wrapping a callback passed to callback', () {
+    expect(rustShapes.stringifyWith(7), equals('kotlin: 7'));
+    expect(rustShapes.stringifyWith(8), equals('kotlin: 8'));
+  });
+
+  test('callbacks stored in optionals, sequences and maps', () {
+    final store = RustStringifierStore();
+    final handles =
+        FfiConverterCallbackInterfaceStoredForeignStringifier.handleCount;
+
+    store.addOptional(StoredDartStringifier());
+    store.addOptional(null);
+    store.addList([StoredDartStringifier(), StoredDartStringifier()]);
+    store.addMap({'a': StoredDartStringifier()});
+    store.addNested([StoredDartStringifier()]);
+    store.addNested(null);
+
+    expect(store.count(), equals(5));
+    expect(store.stringifyAll(7), equals(List.filled(5, 'kotlin: 7')));
+    expect(FfiConverterCallbackInterfaceStoredForeignStringifier.handleCount,
+        equals(handles + 5));
+
+    // Rust dropping the callbacks must release their handles
+    store.clear();
+    expect(store.count(), equals(0));
+    expect(FfiConverterCallbackInterfaceStoredForeignStringifier.handleCount,
+        equals(handles));
+  });
+
   // test('getString throws SimpleException.BadArgument', () {
   //   final v = rustGetters.getString(callback, "BadArgument", true);
   //   expect(v, throwsA(isA<Exception>()));
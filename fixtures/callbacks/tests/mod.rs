@@ -1,6 +1,31 @@
+use std::fs;
+
 use anyhow::Result;
 
 #[test]
 fn callbacks() -> Result<()> {
-    uniffi_dart::testing::run_test("callbacks", "src/api.udl", None)
+    uniffi_dart::testing::run_test("callbacks", "src/api.udl", Some("uniffi.toml"))
+}
+
+#[test]
+fn callbacks_in_records() -> Result<()> {
+    assert!(uniffi_dart::gen::check_udl("src/records.udl".into(), None, None)?.is_empty());
+
+    let out_dir = "../../.tmp_tests/callback_records";
+    let _ = fs::remove_dir_all(out_dir);
+    uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "src/records.udl".into(),
+        None,
+        Some(out_dir.into()),
+        None,
+        None,
+        false,
+    )?;
+
+    let bindings = fs::read_to_string(format!("{out_dir}/src/callback_records.dart"))?;
+    assert!(bindings.contains("final EntryStringifier stringifier;"));
+    assert!(bindings.contains("final EntryStringifier? fallback;"));
+    assert!(bindings
+        .contains("FfiConverterCallbackInterfaceEntryStringifier.write(value.stringifier, "));
+    Ok(())
 }
@@ -2,16 +2,52 @@ import 'package:test/test.dart';
 import '../custom_types.dart';
 
 void main() {
-  final api = Api.load();
-  
-  final demo = api.getCustomTypesDemo(null);
-
-  assert(demo.url.toString() == 'http://example.com/');
-  
-  assert(demo.handle == 123);
-
-  demo.url = Uri.parse('http://new.example.com/');
-  demo.handle = 456;
-  
-  assert(demo == api.getCustomTypesDemo(demo));
+  ensureInitialized();
+
+  test('configured custom types are lifted into their Dart types', () {
+    final demo = getCustomTypesDemo(null);
+
+    expect(demo.url, isA<Uri>());
+    expect(demo.url, equals(Uri.parse('http://example.com/')));
+    expect(demo.timeIntervalMs, equals(Duration(seconds: 456)));
+  });
+
+  test('unconfigured custom types are wrapped in their own class', () {
+    final demo = getCustomTypesDemo(null);
+
+    expect(demo.handle, equals(Handle(123)));
+    expect(demo.handle.value, equals(123));
+    expect(demo.timeIntervalSecDbl, equals(TimeIntervalSecDbl(456.0)));
+    expect(demo.timeIntervalSecFlt, equals(TimeIntervalSecFlt(777.0)));
+  });
+
+  test('wrapper classes compare by value', () {
+    expect(Handle(1), equals(Handle(1)));
+    expect(Handle(1), isNot(equals(Handle(2))));
+    expect(Handle(1).hashCode, equals(Handle(1).hashCode));
+    expect(Handle(1).toString(), equals('Handle(1)'));
+  });
+
+  test('custom types roundtrip through records', () {
+    final demo = CustomTypesDemo(
+      Uri.parse('http://new.example.com/'),
+      Handle(456),
+      Duration(milliseconds: 1500),
+      TimeIntervalSecDbl(1.5),
+      TimeIntervalSecFlt(2.5),
+    );
+    final roundtripped = getCustomTypesDemo(demo);
+
+    expect(roundtripped.url, equals(Uri.parse('http://new.example.com/')));
+    expect(roundtripped.handle, equals(Handle(456)));
+    expect(roundtripped.timeIntervalMs, equals(Duration(milliseconds: 1500)));
+  });
+
+  test('custom types as arguments and in sequences', () {
+    expect(getUrl(Uri.parse('http://example.com/path')).path, equals('/path'));
+    expect(
+      getIntervals([Duration(seconds: 1), Duration(milliseconds: 2)]),
+      equals([Duration(seconds: 1), Duration(milliseconds: 2)]),
+    );
+  });
 }
@@ -0,0 +1,45 @@
+import 'package:test/test.dart';
+import '../custom_types.dart';
+
+void main() {
+  ensureInitialized();
+
+  test('configured custom types are lifted into their Dart types', () {
+    final demo = getCustomTypesDemo(null);
+
+    expect(demo.url, isA<Uri>());
+    expect(demo.url, equals(Uri.parse('http://example.com/')));
+    expect(demo.timeIntervalMs, equals(Duration(seconds: 456)));
+  });
+
+  test('unconfigured custom types stay their builtin', () {
+    final demo = getCustomTypesDemo(null);
+
+    expect(demo.handle, equals(123));
+    expect(demo.timeIntervalSecDbl, equals(456.0));
+    expect(demo.timeIntervalSecFlt, equals(777.0));
+  });
+
+  test('custom types roundtrip through records', () {
+    final demo = CustomTypesDemo(
+      Uri.parse('http://new.example.com/'),
+      456,
+      Duration(milliseconds: 1500),
+      1.5,
+      2.5,
+    );
+    final roundtripped = getCustomTypesDemo(demo);
+
+    expect(roundtripped.url, equals(Uri.parse('http://new.example.com/')));
+    expect(roundtripped.handle, equals(456));
+    expect(roundtripped.timeIntervalMs, equals(Duration(milliseconds: 1500)));
+  });
+
+  test('custom types as arguments and in sequences', () {
+    expect(getUrl(Uri.parse('http://example.com/path')).path, equals('/path'));
+    expect(
+      getIntervals([Duration(seconds: 1), Duration(milliseconds: 2)]),
+      equals([Duration(seconds: 1), Duration(milliseconds: 2)]),
+    );
+  });
+}
@@ -1,6 +1,16 @@
 use anyhow::Result;
 
-// #[test]
-// fn custom_types() -> Result<()> {
-//     uniffi_dart::testing::run_test("custom_types", "src/api.udl", None)
-// }
+#[test]
+fn custom_types() -> Result<()> {
+    uniffi_dart::testing::run_test("custom_types", "src/api.udl", Some("uniffi.toml"))
+}
+
+#[test]
+fn custom_types_typedefs() -> Result<()> {
+    uniffi_dart::testing::run_test_in(
+        "custom_types",
+        "src/api.udl",
+        Some("typedefs.toml"),
+        "test_typedefs",
+    )
+}
@@ -1,11 +1,32 @@
-use uniffi;
-
 use once_cell::sync::Lazy;
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 static LIVE_COUNT: Lazy<RwLock<i32>> = Lazy::new(|| RwLock::new(0));
 
+/// Counts the bytes the library has allocated, so the tests can tell buffers handed to Dart
+/// are freed
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 #[derive(Debug, Clone, uniffi::Object)]
 pub struct Resource {}
 
@@ -17,6 +38,19 @@ impl Resource {
     }
 }
 
+impl Default for Resource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[uniffi::export]
+impl Resource {
+    pub fn is_alive(&self) -> bool {
+        true
+    }
+}
+
 impl Drop for Resource {
     fn drop(&mut self) {
         *LIVE_COUNT.write().unwrap() -= 1;
@@ -82,4 +116,29 @@ fn get_maybe_resource_journal() -> MaybeResourceJournal {
     }
 }
 
+#[uniffi::export]
+fn get_allocated_bytes() -> u64 {
+    ALLOCATED_BYTES.load(Ordering::Relaxed)
+}
+
+#[uniffi::export]
+fn get_text(len: u32) -> String {
+    "x".repeat(len as usize)
+}
+
+#[uniffi::export]
+fn get_bytes(len: u32) -> Vec<u8> {
+    vec![7; len as usize]
+}
+
+#[uniffi::export]
+fn get_texts(count: u32, len: u32) -> Vec<String> {
+    (0..count).map(|_| get_text(len)).collect()
+}
+
+#[uniffi::export]
+fn echo_texts(texts: Vec<String>) -> Vec<String> {
+    texts
+}
+
 uniffi::include_scaffolding!("api");
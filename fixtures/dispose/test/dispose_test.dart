@@ -2,40 +2,114 @@ import 'package:test/test.dart';
 import '../dispose.dart';
 
 void main() {
-  final api = Api.load();
-
   test('ObjectDecrementsLiveCount', () {
-    final resource = api.getResource();
-    expect(api.getLiveCount(), 1);
+    final resource = getResource();
+    expect(getLiveCount(), 1);
     resource.dispose();
-    expect(api.getLiveCount(), 0);
+    expect(getLiveCount(), 0);
   });
 
   test('MapDecrementsLiveCount', () {
-    final journal = api.getResourceJournalMap();
-    expect(api.getLiveCount(), 2);
-    journal.dispose();
-    expect(api.getLiveCount(), 0);
+    final journal = getResourceJournalMap();
+    expect(getLiveCount(), 2);
+    journal.resources.values.forEach((resource) => resource.dispose());
+    expect(getLiveCount(), 0);
   });
 
   test('ListDecrementsLiveCount', () {
-    final journal = api.getResourceJournalList();
-    expect(api.getLiveCount(), 2);
-    journal.dispose();
-    expect(api.getLiveCount(), 0);
+    final journal = getResourceJournalList();
+    expect(getLiveCount(), 2);
+    journal.resources.forEach((resource) => resource.dispose());
+    expect(getLiveCount(), 0);
   });
 
   test('MapListDecrementsLiveCount', () {
-    final journal = api.getResourceJournalMapList();
-    expect(api.getLiveCount(), 2);
-    journal.dispose();
-    expect(api.getLiveCount(), 0);
+    final journal = getResourceJournalMapList();
+    expect(getLiveCount(), 2);
+    for (final resources in journal.resources.values) {
+      resources?.forEach((resource) => resource.dispose());
+    }
+    expect(getLiveCount(), 0);
   });
 
   test('EnumDecrementsLiveCount', () {
-    final maybeJournal = api.getMaybeResourceJournal();
-    expect(api.getLiveCount(), 2);
-    maybeJournal.dispose();
-    expect(api.getLiveCount(), 0);
+    final maybeJournal = getMaybeResourceJournal();
+    expect(getLiveCount(), 2);
+    expect(maybeJournal, isA<SomeMaybeResourceJournal>());
+    (maybeJournal as SomeMaybeResourceJournal)
+        .resource
+        .resources
+        .forEach((resource) => resource.dispose());
+    expect(getLiveCount(), 0);
+  });
+
+  test('DisposeTwiceIsNoop', () {
+    final resource = getResource();
+    final other = getResource();
+    resource.dispose();
+    resource.dispose();
+    expect(getLiveCount(), 1);
+    other.dispose();
+    expect(getLiveCount(), 0);
+  });
+
+  test('UseAfterDisposeThrows', () {
+    final resource = getResource();
+    expect(resource.isAlive(), isTrue);
+    resource.dispose();
+    expect(() => resource.isAlive(), throwsStateError);
+  });
+
+  test('UseDisposesAfterAction', () {
+    final alive = getResource().use((resource) {
+      expect(getLiveCount(), 1);
+      return resource.isAlive();
+    });
+    expect(alive, isTrue);
+    expect(getLiveCount(), 0);
+  });
+
+  test('UseDisposesWhenActionThrows', () {
+    expect(
+        () => getResource().use((resource) => throw StateError('failed')),
+        throwsStateError);
+    expect(getLiveCount(), 0);
+  });
+
+  test('UseDisposesOnceFutureCompletes', () async {
+    final future = getResource().use((resource) async {
+      await Future<void>.delayed(Duration.zero);
+      return resource.isAlive();
+    });
+    expect(getLiveCount(), 1);
+    expect(await future, isTrue);
+    expect(getLiveCount(), 0);
+  });
+
+  test('LiftedBuffersAreFreed', () {
+    final before = getAllocatedBytes();
+    for (var i = 0; i < 100; i++) {
+      expect(getText(100000).length, 100000);
+      expect(getBytes(100000).length, 100000);
+      expect(getTexts(10, 10000).length, 10);
+    }
+    // Leaking the buffers would keep tens of megabytes allocated
+    expect(getAllocatedBytes() - before, lessThan(1000000));
+  });
+
+  test('LiftedBytesOutliveTheirBuffer', () {
+    final bytes = getBytes(64);
+    getText(64);
+    getBytes(64);
+    expect(bytes, everyElement(7));
+  });
+
+  test('LoweredBuffersAreFreed', () {
+    final texts = List.generate(10, (i) => 'é' * (i * 1000));
+    final before = getAllocatedBytes();
+    for (var i = 0; i < 100; i++) {
+      expect(echoTexts(texts), texts);
+    }
+    expect(getAllocatedBytes() - before, lessThan(1000000));
   });
 }
@@ -1,6 +1,6 @@
-// use anyhow::Result;
+use anyhow::Result;
 
-// #[test]
-// fn dispose() -> Result<()> {
-//     uniffi_dart::testing::run_test("dispose", "src/api.udl", None)
-// }
+#[test]
+fn dispose() -> Result<()> {
+    uniffi_dart::testing::run_test("dispose", "src/api.udl", None)
+}
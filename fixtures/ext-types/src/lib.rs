@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use ext_types_sub::{Color, Counter, Label, Point};
+
+fn translate(point: Point, dx: i32, dy: i32) -> Point {
+    Point {
+        x: point.x + dx,
+        y: point.y + dy,
+    }
+}
+
+fn maybe_point(present: bool) -> Option<Point> {
+    present.then_some(Point { x: 1, y: 1 })
+}
+
+fn diagonal(count: i32) -> Vec<Point> {
+    (0..count).map(|i| Point { x: i, y: i }).collect()
+}
+
+fn next_color(color: Color) -> Color {
+    match color {
+        Color::Red => Color::Green,
+        Color::Green => Color::Blue,
+        Color::Blue => Color::Red,
+    }
+}
+
+fn bump(counter: Arc<Counter>) -> u32 {
+    counter.increment()
+}
+
+// UDL can't refer to custom types of other crates
+#[uniffi::export]
+fn shout(label: Label) -> Label {
+    Label(label.0.to_uppercase())
+}
+
+uniffi::include_scaffolding!("api");
@@ -0,0 +1,40 @@
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Debug, Clone, Copy, uniffi::Enum)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+#[derive(Debug, Default, uniffi::Object)]
+pub struct Counter {
+    value: Mutex<u32>,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub fn increment(&self) -> u32 {
+        let mut value = self.value.lock().unwrap();
+        *value += 1;
+        *value
+    }
+}
+
+pub struct Label(pub String);
+
+uniffi::custom_newtype!(Label, String);
+
+uniffi::setup_scaffolding!();
+uniffi_dart::setup_scaffolding!();
@@ -0,0 +1,23 @@
+import 'package:test/test.dart';
+import '../ext_types_all.dart';
+
+void main() {
+  ensureInitialized();
+
+  test('types of both components through the barrel', () {
+    final moved = translate(Point(1, 2), 3, 4);
+    expect(moved.x, equals(4));
+    expect(nextColor(Color.blue), equals(Color.red));
+  });
+
+  test('objects of one component passed to the other', () {
+    final counter = Counter();
+    expect(bump(counter), equals(1));
+  });
+
+  test('one runtime for both components', () {
+    expect(diagonal(2).length, equals(2));
+    expect(const UniffiInternalError(UniffiInternalError.rustPanic, 'x').toString(),
+        equals('UniFfi::rustPanic: x'));
+  });
+}
@@ -0,0 +1,33 @@
+import 'package:test/test.dart';
+import '../ext_types.dart';
+import '../ext_types_sub.dart' as sub;
+
+void main() {
+  ensureInitialized();
+
+  test('external record', () {
+    final moved = translate(Point(1, 2), 3, 4);
+    expect(moved.x, equals(4));
+    expect(moved.y, equals(6));
+  });
+
+  test('external record in optionals and sequences', () {
+    expect(maybePoint(false), isNull);
+    expect(maybePoint(true)!.x, equals(1));
+    expect(diagonal(3).map((p) => p.y).toList(), equals([0, 1, 2]));
+  });
+
+  test('external enum', () {
+    expect(nextColor(Color.blue), equals(Color.red));
+  });
+
+  test('external object created by the other bindings', () {
+    final counter = sub.Counter();
+    expect(bump(counter), equals(1));
+    expect(bump(counter), equals(2));
+  });
+
+  test('external custom type', () {
+    expect(shout('hello'), equals('HELLO'));
+  });
+}
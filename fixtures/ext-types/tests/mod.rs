@@ -0,0 +1,34 @@
+use std::fs;
+
+use anyhow::Result;
+
+#[test]
+fn ext_types() -> Result<()> {
+    uniffi_dart::testing::run_library_test("ext_types", "src/api.udl", None)
+}
+
+#[test]
+fn external_packages() -> Result<()> {
+    let out_dir = "../../.tmp_tests/ext_types_packages";
+    let _ = fs::remove_dir_all(out_dir);
+    uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "src/api.udl".into(),
+        Some("packages.toml".into()),
+        Some(out_dir.into()),
+        None,
+        None,
+        false,
+    )?;
+
+    // Types of the sub-crate come from the internal layer of the package it is mapped to
+    let bindings = fs::read_to_string(format!("{out_dir}/src/ext_types.dart"))?;
+    assert!(bindings.contains(
+        "import \"package:ext_types_sub_bindings/src/ext_types_sub.dart\" as ext_types_sub;"
+    ));
+    assert!(bindings.contains("typedef FfiConverterPoint = ext_types_sub.FfiConverterPoint;"));
+
+    // Without a package name, the public library is left unnamed
+    let library = fs::read_to_string(format!("{out_dir}/ext_types.dart"))?;
+    assert!(library.starts_with("library;"));
+    Ok(())
+}
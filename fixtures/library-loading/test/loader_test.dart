@@ -0,0 +1,20 @@
+import 'dart:ffi';
+import 'dart:io';
+
+import 'package:test/test.dart';
+import '../library_loading.dart';
+
+void main() {
+  var loads = 0;
+  setLibraryLoader(() {
+    loads++;
+    return DynamicLibrary.open('${Directory.current.path}/liblibrary_loading.so');
+  });
+
+  test('library opened by the loader hook', () {
+    initialize();
+    ensureInitialized();
+    expect(add(20, 22), equals(42));
+    expect(loads, equals(1));
+  });
+}
@@ -0,0 +1,9 @@
+import 'package:test/test.dart';
+import '../library_loading.dart';
+
+void main() {
+  test('library found in the configured search paths', () {
+    ensureInitialized();
+    expect(add(2, 3), equals(5));
+  });
+}
@@ -0,0 +1,6 @@
+use anyhow::Result;
+
+#[test]
+fn library_loading() -> Result<()> {
+    uniffi_dart::testing::run_test("library_loading", "src/api.udl", Some("uniffi.toml"))
+}
@@ -0,0 +1,26 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn add(left: u32, right: u32) -> u32 {
+    left + right
+}
+
+fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+#[derive(Debug, Default)]
+pub struct Counter {
+    count: AtomicU32,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Counter::default()
+    }
+
+    fn increment(&self) -> u32 {
+        self.count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
+uniffi::include_scaffolding!("api");
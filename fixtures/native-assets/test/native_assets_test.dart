@@ -0,0 +1,17 @@
+import 'package:test/test.dart';
+import '../native_assets.dart';
+
+void main() {
+  ensureInitialized();
+
+  test('functions resolved from the code asset', () {
+    expect(add(2, 3), equals(5));
+    expect(greet('assets'), equals('Hello, assets!'));
+  });
+
+  test('objects resolved from the code asset', () {
+    final counter = Counter();
+    expect(counter.increment(), equals(1));
+    expect(counter.increment(), equals(2));
+  });
+}
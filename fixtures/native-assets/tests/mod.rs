@@ -0,0 +1,6 @@
+use anyhow::Result;
+
+#[test]
+fn native_assets() -> Result<()> {
+    uniffi_dart::testing::run_test("native_assets", "src/api.udl", Some("uniffi.toml"))
+}
@@ -0,0 +1,5 @@
+fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+uniffi::include_scaffolding!("api");
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+
+#[test]
+fn package_layout() -> Result<()> {
+    let out_dir = "../../.tmp_tests/package_layout";
+    let _ = fs::remove_dir_all(out_dir);
+    uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "src/api.udl".into(),
+        Some("uniffi.toml".into()),
+        Some(out_dir.into()),
+        None,
+        None,
+        false,
+    )?;
+
+    let pubspec = fs::read_to_string(format!("{out_dir}/pubspec.yaml"))?;
+    assert!(pubspec.starts_with("name: greeter\n"));
+    assert!(pubspec.contains("version: 1.2.3\n"));
+    assert!(pubspec.contains("  ffi: ^2.0.1\n"));
+    assert!(Path::new(out_dir).join("analysis_options.yaml").exists());
+
+    // The bindings stay private to the package, behind the library named after it, which
+    // only exports their user-facing API
+    let library = fs::read_to_string(format!("{out_dir}/lib/greeter.dart"))?;
+    assert!(library.contains("export \"src/package_layout.dart\" show "));
+    assert!(library.contains(" greet"));
+    assert!(!library.contains("RustBuffer"));
+    assert!(!library.contains("FfiConverter"));
+    let bindings = fs::read_to_string(format!("{out_dir}/lib/src/package_layout.dart"))?;
+    assert!(bindings.contains("String greet("));
+    // `expose_ffi` adds a library exporting all of it
+    let ffi = fs::read_to_string(format!("{out_dir}/lib/package_layout_ffi.dart"))?;
+    assert!(ffi.contains("export \"src/package_layout.dart\";"));
+    Ok(())
+}
+
+#[test]
+fn flutter_plugin() -> Result<()> {
+    let out_dir = "../../.tmp_tests/package_layout_plugin";
+    let _ = fs::remove_dir_all(out_dir);
+    uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "src/api.udl".into(),
+        Some("flutter.toml".into()),
+        Some(out_dir.into()),
+        None,
+        None,
+        false,
+    )?;
+
+    let pubspec = fs::read_to_string(format!("{out_dir}/pubspec.yaml"))?;
+    assert!(pubspec.contains("  flutter:\n    sdk: flutter\n"));
+    assert!(pubspec.contains("      ios:\n        ffiPlugin: true\n"));
+
+    let rust_cmake = fs::read_to_string(format!("{out_dir}/src/rust.cmake"))?;
+    assert!(rust_cmake.contains("${CMAKE_CURRENT_LIST_DIR}/../../rust/Cargo.toml"));
+    let linux = fs::read_to_string(format!("{out_dir}/linux/CMakeLists.txt"))?;
+    assert!(linux.contains("set(greeter_plugin_bundled_libraries"));
+    for platform in ["ios", "macos"] {
+        let podspec = fs::read_to_string(format!("{out_dir}/{platform}/greeter_plugin.podspec"))?;
+        assert!(podspec.contains("src/build_apple.sh"));
+    }
+
+    // The library comes with the app rather than from the working directory
+    let bindings = fs::read_to_string(format!("{out_dir}/lib/src/package_layout.dart"))?;
+    assert!(bindings.contains("return DynamicLibrary.process();"));
+    assert!(!bindings.contains("Directory.current.path"));
+    Ok(())
+}
@@ -59,4 +59,10 @@ impl MyHashSet {
     }
 }
 
+impl Default for MyHashSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 uniffi::include_scaffolding!("api");
@@ -29,6 +29,11 @@ void main() {
       expect(hashMapIdentity(testMap), testMap);
     });
 
+    test('hash_map_identity keeps non-ASCII keys and values', () {
+      final testMap = {'clé': 'välue', '世界': '🌍'};
+      expect(hashMapIdentity(testMap), testMap);
+    });
+
     test('empty map identity', () {
       final emptyMap = <String, String>{};
       expect(hashMapIdentity(emptyMap), emptyMap);
@@ -1,6 +1,5 @@
 use async_stream::stream;
 use futures::stream::{self, Stream, StreamExt};
-use futures::TryStreamExt;
 use std::pin::Pin;
 use tokio::time::{interval, Duration};
 
@@ -185,6 +185,10 @@ void main() {
         expect(takeString('Hello, 世界!'), 'Hello, 世界!');
         expect(takeString('🌍🌎🌏'), '🌍🌎🌏');
       });
+
+      test('unpaired surrogates become replacement characters', () {
+        expect(takeString('a\uD800b\uDC00'), 'a\uFFFDb\uFFFD');
+      });
     });
 
     group('Bytes Tests', () {
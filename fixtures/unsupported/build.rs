@@ -0,0 +1,4 @@
+fn main() {
+    // Only the scaffolding: generating Dart bindings would fail on the unsupported items
+    uniffi::generate_scaffolding("./src/api.udl").unwrap();
+}
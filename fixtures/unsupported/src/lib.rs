@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+pub fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+pub fn now() -> SystemTime {
+    SystemTime::now()
+}
+
+pub struct Event {
+    pub name: String,
+    pub counts: HashMap<String, u32>,
+}
+
+pub struct Clock {
+    ticks: u64,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock { ticks: 0 }
+    }
+
+    pub fn starting_at(ticks: u64) -> Self {
+        Clock { ticks }
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub trait Shape: Send + Sync {
+    fn area(&self) -> f64;
+}
+
+uniffi::include_scaffolding!("api");
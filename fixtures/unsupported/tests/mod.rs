@@ -0,0 +1,81 @@
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+
+use anyhow::Result;
+
+#[test]
+fn unsupported() -> Result<()> {
+    let unsupported = uniffi_dart::gen::check_udl("src/api.udl".into(), None, None)?
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+
+    // The map field of `Event` is generated like any other, so it isn't reported
+    assert_eq!(
+        unsupported,
+        [
+            "unsupported::now: return type: timestamps are not supported",
+            "unsupported::Clock::now: return type: timestamps are not supported",
+            "unsupported::Shape: trait interface `Shape` is not supported, only foreign-implementable traits are",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn print_repr() -> Result<()> {
+    let ci = uniffi_dart::gen::component_from_udl("src/api.udl".into(), None, None)?;
+    let repr = uniffi_dart::gen::ComponentRepr::from(&ci);
+
+    let clock = &repr.objects[0];
+    assert_eq!(clock.name, "Clock");
+    assert_eq!(clock.ffi_free_symbol, "uniffi_unsupported_fn_free_clock");
+    // The primary constructor is the unnamed one
+    assert_eq!(clock.constructors[0].dart_name, "Clock");
+    assert_eq!(clock.constructors[1].dart_name, "Clock.startingAt");
+    assert_eq!(
+        clock.constructors[1].ffi_symbol,
+        "uniffi_unsupported_fn_constructor_clock_starting_at"
+    );
+    assert_eq!(clock.methods[1].name, "ticks");
+    assert_eq!(
+        clock.methods[1]
+            .return_type
+            .as_ref()
+            .unwrap()
+            .dart_type
+            .as_deref(),
+        Some("int")
+    );
+    // Unsupported types are reported rather than mapped
+    let now = clock.methods[0].return_type.as_ref().unwrap();
+    assert_eq!(now.dart_type, None);
+    assert_eq!(
+        now.unsupported.as_deref(),
+        Some("timestamps are not supported")
+    );
+
+    let json = uniffi_dart::gen::print_repr(&[ci])?;
+    assert!(json.contains("\"ffi_converter\": \"FfiConverterString\""));
+    Ok(())
+}
+
+#[test]
+fn objects_need_finalizers() -> Result<()> {
+    // The crate is built with plain uniffi, so its library lacks the functions freeing `Clock`
+    let target_dir = std::env::current_exe()?;
+    let target_dir = target_dir.ancestors().nth(2).unwrap();
+    let library = target_dir.join(format!("{DLL_PREFIX}unsupported{DLL_SUFFIX}"));
+    let err = uniffi_dart::gen::generate_dart_bindings_from_udl(
+        "src/api.udl".into(),
+        None,
+        Some("../../.tmp_tests/unsupported".into()),
+        Some(library.to_str().unwrap().into()),
+        None,
+        false,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("lacks the finalizer functions freeing the objects of `unsupported`"));
+    assert!(err.contains("uniffi_dart::setup_scaffolding!()"));
+    Ok(())
+}
@@ -1,4 +1,170 @@
-fn main() {
-    println!("uniffi_bindgen_dart binary");
-    println!("This binary is a placeholder and will be implemented in the future.");
+use anyhow::{bail, Result};
+use camino::Utf8PathBuf;
+use clap::{Args, Parser, Subcommand};
+use uniffi_bindgen::cargo_metadata::CrateConfigSupplier;
+use uniffi_bindgen::ComponentInterface;
+
+#[derive(Parser)]
+#[command(
+    name = "uniffi_bindgen_dart",
+    version,
+    about = "Dart bindings generator for UniFFI"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Generate Dart bindings
+    Generate {
+        #[command(flatten)]
+        source: Source,
+
+        /// Directory the bindings are written to
+        #[arg(short, long)]
+        out_dir: Option<Utf8PathBuf>,
+
+        /// Path to an optional uniffi config file, overriding the crate's `uniffi.toml`
+        #[arg(short, long)]
+        config: Option<Utf8PathBuf>,
+
+        /// Skip running `dart format` on the generated code
+        #[arg(long)]
+        no_format: bool,
+    },
+    /// List the functions and types the Dart backend can't generate bindings for,
+    /// exiting with a non-zero status if there are any
+    Check {
+        #[command(flatten)]
+        source: Source,
+    },
+    /// Print the component interface and how each item maps onto Dart as JSON
+    PrintRepr {
+        #[command(flatten)]
+        source: Source,
+    },
+    /// Write the shared runtime package imported by bindings configured with `runtime_package`
+    Runtime {
+        /// Directory the package is written to
+        out_dir: Utf8PathBuf,
+
+        /// Name of the Dart package, which must match the `runtime_package` setting
+        #[arg(long, default_value = "uniffi_dart_runtime")]
+        package_name: String,
+
+        /// Skip running `dart format` on the generated code
+        #[arg(long)]
+        no_format: bool,
+    },
+}
+
+/// Where the component interface is read from
+#[derive(Args)]
+struct Source {
+    /// Path to the UDL file, or to the compiled library with `--library`
+    source: Utf8PathBuf,
+
+    /// Treat `source` as a compiled library and read the interface from its metadata
+    #[arg(long)]
+    library: bool,
+
+    /// Compiled library used in UDL mode to resolve the cdylib name and proc-macro items
+    #[arg(long = "lib-file")]
+    lib_file: Option<Utf8PathBuf>,
+
+    /// Only handle this crate
+    #[arg(long = "crate")]
+    crate_name: Option<String>,
+
+    /// Pass `--no-deps` to `cargo metadata` when locating crates in library mode
+    #[arg(long)]
+    metadata_no_deps: bool,
+}
+
+impl Source {
+    fn components(&self) -> Result<Vec<ComponentInterface>> {
+        if self.library {
+            uniffi_dart::gen::components_from_library(
+                &self.source,
+                self.crate_name.as_deref(),
+                &self.config_supplier()?,
+            )
+        } else {
+            Ok(vec![uniffi_dart::gen::component_from_udl(
+                &self.source,
+                self.lib_file.as_deref(),
+                self.crate_name.as_deref(),
+            )?])
+        }
+    }
+
+    fn config_supplier(&self) -> Result<CrateConfigSupplier> {
+        if self.lib_file.is_some() {
+            bail!("--lib-file is only valid in UDL mode");
+        }
+        uniffi_dart::gen::cargo_metadata_config_supplier(None, self.metadata_no_deps)
+    }
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Commands::Generate {
+            source,
+            out_dir,
+            config,
+            no_format,
+        } => {
+            if source.library {
+                let Some(out_dir) = out_dir else {
+                    bail!("--out-dir is required with --library");
+                };
+                uniffi_dart::gen::generate_dart_bindings_from_library(
+                    &source.source,
+                    source.crate_name.clone(),
+                    &source.config_supplier()?,
+                    config.as_deref(),
+                    &out_dir,
+                    !no_format,
+                )
+            } else {
+                uniffi_dart::gen::generate_dart_bindings_from_udl(
+                    &source.source,
+                    config.as_deref(),
+                    out_dir.as_deref(),
+                    source.lib_file.as_deref(),
+                    source.crate_name.as_deref(),
+                    !no_format,
+                )
+            }
+        }
+        Commands::Check { source } => {
+            let unsupported: Vec<_> = source
+                .components()?
+                .iter()
+                .flat_map(uniffi_dart::gen::check_component)
+                .collect();
+            if unsupported.is_empty() {
+                println!("No unsupported items found");
+                return Ok(());
+            }
+            for item in &unsupported {
+                eprintln!("{item}");
+            }
+            bail!(
+                "{} item(s) are not supported by the Dart backend",
+                unsupported.len()
+            );
+        }
+        Commands::PrintRepr { source } => {
+            println!("{}", uniffi_dart::gen::print_repr(&source.components()?)?);
+            Ok(())
+        }
+        Commands::Runtime {
+            out_dir,
+            package_name,
+            no_format,
+        } => uniffi_dart::gen::generate_runtime_package(&out_dir, &package_name, !no_format),
+    }
 }
@@ -1,10 +1,24 @@
 use anyhow::{Context, Result};
 use camino::Utf8Path;
 use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
 
 pub fn generate_scaffolding(udl_file: &Utf8Path) -> Result<()> {
     uniffi_build::generate_scaffolding(udl_file)?;
     let out_dir = env::var("OUT_DIR").context("$OUT_DIR missing?!")?;
+    // The bindings free objects through the finalizer functions of the library they load,
+    // named after the crate building it
+    let libname = crate::gen::component_from_udl(udl_file, None, None)?
+        .crate_name()
+        .to_string();
+    let file_stem = udl_file.file_stem().context("not a file")?;
+    let scaffolding = Utf8Path::new(&out_dir).join(format!("{file_stem}.uniffi.rs"));
+    OpenOptions::new()
+        .append(true)
+        .open(&scaffolding)
+        .with_context(|| format!("Failed to open {scaffolding}"))?
+        .write_all(crate::gen::finalizer::rust_scaffolding(&libname).as_bytes())?;
     uniffi_bindgen::generate_external_bindings(
         &crate::gen::DartBindingGenerator {},
         udl_file,
@@ -1,7 +1,6 @@
-use crate::gen::CodeType;
+use crate::gen::{CallbackMode, CodeType};
 use genco::prelude::*;
-use uniffi_bindgen::interface::Type;
-use uniffi_bindgen::interface::{AsType, Method};
+use uniffi_bindgen::interface::{AsType, FfiType, Method, Type};
 
 use crate::gen::oracle::{AsCodeType, DartCodeOracle};
 use crate::gen::render::AsRenderable;
@@ -31,7 +30,7 @@ impl CodeType for CallbackInterfaceCodeType {
     }
 
     fn initialization_fn(&self) -> Option<String> {
-        Some(format!("_uniffiInitializeCallbackInterface{}", self.name))
+        Some(vtable_init_fn_name(&self.name))
     }
 }
 
@@ -46,6 +45,7 @@ impl Renderable for CallbackInterfaceCodeType {
         // Generate all necessary components for the callback interface
         let interface = generate_callback_interface(
             callback.name(),
+            callback.docstring(),
             &callback.as_codetype().ffi_converter_name(),
             &callback.methods(),
             type_helper,
@@ -73,18 +73,25 @@ impl Renderable for CallbackInterfaceCodeType {
     }
 }
 
+/// Name of the function registering the vtable for `callback_name` with Rust
+pub fn vtable_init_fn_name(callback_name: &str) -> String {
+    format!("_uniffiInitializeCallbackInterface{callback_name}")
+}
+
 pub fn generate_callback_interface(
     callback_name: &str,
+    docstring: Option<&str>,
     ffi_converter_name: &str,
     methods: &[&Method],
     type_helper: &dyn TypeHelperRenderer,
 ) -> dart::Tokens {
     let cls_name = &DartCodeOracle::class_name(callback_name);
     let ffi_conv_name = &DartCodeOracle::class_name(ffi_converter_name);
-    let init_fn_name = &format!("init{callback_name}VTable");
+    let init_fn_name = &vtable_init_fn_name(callback_name);
 
     let tokens = quote! {
         // This is the abstract class to be implemented
+        $(DartCodeOracle::doc_comment(docstring))
         abstract class $cls_name {
             $(for m in methods {
                 $(generate_callback_methods_definitions(m, type_helper))
@@ -105,6 +112,9 @@ pub fn generate_callback_interface(
                 return _handleMap.insert(value);
             }
 
+            // Number of callbacks currently held by Rust
+            static int get handleCount => _handleMap.length;
+
             static void _ensureVTableInitialized() {
                 if (!_vtableInitialized) {
                     $init_fn_name();
@@ -158,6 +168,7 @@ fn generate_callback_methods_definitions(
     };
 
     quote!(
+        $(DartCodeOracle::doc_comment(method.docstring()))
         $ret_type $method_name($(for a in dart_args => $a,));
     )
 }
@@ -176,19 +187,16 @@ fn generate_callback_methods_signatures(
         let dart_method_type =
             format!("UniffiCallbackInterface{callback_name}Method{method_index}Dart");
 
-        let method_return_type = if let Some(ret) = method.return_type() {
-            DartCodeOracle::native_type_label(Some(ret), type_helper.get_ci())
-        } else {
-            quote!(Void)
-        };
+        let out_return_type =
+            DartCodeOracle::callback_out_return_type(method.return_type(), type_helper.get_ci());
 
         tokens.append(quote! {
             typedef $ffi_method_type = Void Function(
-                Uint64, $(for arg in &method.arguments() => $(DartCodeOracle::native_type_label(Some(&arg.as_type()), type_helper.get_ci())),)
-                Pointer<$(&method_return_type)>, Pointer<RustCallStatus>);
+                Uint64, $(for arg in &method.arguments() => $(DartCodeOracle::ffi_native_type_label(Some(&FfiType::from(&arg.as_type())), type_helper.get_ci())),)
+                $(&out_return_type), Pointer<RustCallStatus>);
             typedef $dart_method_type = void Function(
-                int, $(for arg in &method.arguments() => $(DartCodeOracle::native_dart_type_label(Some(&arg.as_type()), type_helper.get_ci())),)
-                Pointer<$(&method_return_type)>, Pointer<RustCallStatus>);
+                int, $(for arg in &method.arguments() => $(DartCodeOracle::ffi_dart_type_label(Some(&FfiType::from(&arg.as_type())), type_helper.get_ci())),)
+                $(&out_return_type), Pointer<RustCallStatus>);
         });
     }
 
@@ -242,24 +250,44 @@ pub fn generate_callback_functions(
         }).collect();
 
         // Prepare arg names for the method call using indexes
-        let arg_names: Vec<dart::Tokens> = m.arguments().iter().enumerate().map(|(arg_idx, arg)| {
-            DartCodeOracle::callback_arg_name(&arg.as_type(), arg_idx)
-        }).collect();
+        let arg_names: Vec<dart::Tokens> = (0..m.arguments().len())
+            .map(DartCodeOracle::callback_arg_name)
+            .collect();
 
         // Handle return value using the oracle
         let call_dart_method = if let Some(ret) = m.return_type() {
-            DartCodeOracle::callback_return_handling(ret, method_name, arg_names)
+            DartCodeOracle::callback_return_handling(ret, method_name, arg_names.clone())
         } else {
             // Handle void return types
-            DartCodeOracle::callback_void_handling(method_name, arg_names)
+            DartCodeOracle::callback_void_handling(method_name, arg_names.clone())
         };
 
         // Get the appropriate out return type
-        let out_return_type = DartCodeOracle::callback_out_return_type(m.return_type());
+        let out_return_type =
+            DartCodeOracle::callback_out_return_type(m.return_type(), type_helper.get_ci());
 
         // Generate the function body
         let callback_method_name = &format!("{}{}", &DartCodeOracle::fn_name(callback_name), &DartCodeOracle::class_name(m.name()));
 
+        // Generation refuses listener mode for methods reporting back to Rust, so these are fire-and-forget
+        if type_helper.get_config().callback_mode() == CallbackMode::Listener {
+            return quote! {
+                void $callback_method_name(int uniffiHandle, $(for param in &param_types => $param,) $out_return_type outReturn, Pointer<RustCallStatus> callStatus) {
+                    // Rust has already returned by the time this runs, so the out-pointers must not be touched
+                    // and errors surface as uncaught errors on the isolate.
+                    final obj = FfiConverterCallbackInterface$cls_name._handleMap.get(uniffiHandle);
+                    $(arg_lifts)
+                    obj.$method_name($(for arg in &arg_names => $arg,));
+                }
+
+                final NativeCallable<$ffi_method_type> $(callback_method_name)Callable =
+                    NativeCallable<$ffi_method_type>.listener($callback_method_name)..keepIsolateAlive = false;
+
+                final Pointer<NativeFunction<$ffi_method_type>> $(callback_method_name)Pointer =
+                    $(callback_method_name)Callable.nativeFunction;
+            };
+        }
+
         quote! {
             void $callback_method_name(int uniffiHandle, $(for param in &param_types => $param,) $out_return_type outReturn, Pointer<RustCallStatus> callStatus) {
                 final status = callStatus.ref;
@@ -283,6 +311,21 @@ pub fn generate_callback_functions(
     let free_callback_pointer = &format!("{}FreePointer", DartCodeOracle::fn_name(callback_name));
     let free_callback_type = &format!("UniffiCallbackInterface{callback_name}Free");
 
+    // Rust frees callbacks from whichever thread drops them last
+    let free_callback_binding = match type_helper.get_config().callback_mode() {
+        CallbackMode::IsolateLocal => quote! {
+            final Pointer<NativeFunction<$free_callback_type>> $free_callback_pointer =
+                Pointer.fromFunction<$free_callback_type>($free_callback_fn);
+        },
+        CallbackMode::Listener => quote! {
+            final NativeCallable<$free_callback_type> $(free_callback_fn)Callable =
+                NativeCallable<$free_callback_type>.listener($free_callback_fn)..keepIsolateAlive = false;
+
+            final Pointer<NativeFunction<$free_callback_type>> $free_callback_pointer =
+                $(free_callback_fn)Callable.nativeFunction;
+        },
+    };
+
     quote! {
         $(functions)
 
@@ -294,8 +337,7 @@ pub fn generate_callback_functions(
             }
         }
 
-        final Pointer<NativeFunction<$free_callback_type>> $free_callback_pointer =
-            Pointer.fromFunction<$free_callback_type>($free_callback_fn);
+        $free_callback_binding
     }
 }
 
@@ -307,11 +349,16 @@ pub fn generate_callback_interface_vtable_init_function(
     let vtable_name = &format!("UniffiVTableCallbackInterface{callback_name}");
     let vtable_static_instance_name =
         format!("{}{}", DartCodeOracle::fn_name(callback_name), "VTable");
-    let init_fn_name = &format!("init{callback_name}VTable");
+    let init_fn_name = &vtable_init_fn_name(callback_name);
+    let public_init_fn_name = &format!("init{callback_name}VTable");
 
     quote! {
         late final Pointer<$vtable_name> $(&vtable_static_instance_name);
 
+        // For FFI-level callers registering the vtable by hand; `ensureInitialized` already does
+        // it, so only the internal library has it
+        void $public_init_fn_name() => $init_fn_name();
+
         void $init_fn_name() {
             // Make initialization idempotent - return early if already initialized
             if (FfiConverterCallbackInterface$(DartCodeOracle::class_name(callback_name))._vtableInitialized) {
@@ -0,0 +1,207 @@
+use std::fmt;
+
+use anyhow::Result;
+use camino::Utf8Path;
+use uniffi_bindgen::interface::{Argument, AsType, Method, ObjectImpl, Type};
+use uniffi_bindgen::{BindgenCrateConfigSupplier, ComponentInterface};
+
+use super::component::{component_from_udl, components_from_library};
+
+/// A function or type the Dart backend can't generate bindings for yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsupportedItem {
+    /// Rust path of the offending item, e.g. `my_crate::MyObject::my_method`
+    pub path: String,
+    pub reason: String,
+}
+
+impl fmt::Display for UnsupportedItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.reason)
+    }
+}
+
+/// Why `ty` can't be rendered, if it can't
+pub(super) fn unsupported_reason(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Timestamp => Some("timestamps are not supported".to_string()),
+        Type::Object {
+            name,
+            imp: ObjectImpl::Trait,
+            ..
+        } => Some(format!(
+            "trait interface `{name}` is not supported, only foreign-implementable traits are"
+        )),
+        Type::Optional { inner_type } | Type::Sequence { inner_type } => {
+            unsupported_reason(inner_type)
+        }
+        Type::Map {
+            key_type,
+            value_type,
+        } => unsupported_reason(key_type).or_else(|| unsupported_reason(value_type)),
+        Type::Custom { builtin, .. } => unsupported_reason(builtin),
+        _ => None,
+    }
+}
+
+fn type_path(ty: &Type) -> String {
+    match ty {
+        Type::Object {
+            module_path, name, ..
+        }
+        | Type::Record { module_path, name }
+        | Type::Enum { module_path, name }
+        | Type::CallbackInterface { module_path, name }
+        | Type::Custom {
+            module_path, name, ..
+        } => format!("{module_path}::{name}"),
+        _ => format!("{ty:?}"),
+    }
+}
+
+#[derive(Default)]
+struct Checker {
+    items: Vec<UnsupportedItem>,
+}
+
+impl Checker {
+    fn report(&mut self, path: String, reason: String) {
+        self.items.push(UnsupportedItem { path, reason });
+    }
+
+    fn check_type(&mut self, path: &str, context: &str, ty: &Type) {
+        if let Some(reason) = unsupported_reason(ty) {
+            self.report(path.to_string(), format!("{context}: {reason}"));
+        }
+    }
+
+    fn check_callable<'a>(
+        &mut self,
+        path: &str,
+        arguments: impl IntoIterator<Item = &'a Argument>,
+        return_type: Option<&Type>,
+        throws_type: Option<&Type>,
+    ) {
+        for arg in arguments {
+            self.check_type(path, &format!("argument `{}`", arg.name()), &arg.as_type());
+        }
+        if let Some(ty) = return_type {
+            self.check_type(path, "return type", ty);
+        }
+        if let Some(ty) = throws_type {
+            self.check_type(path, "error type", ty);
+        }
+    }
+
+    fn check_methods(&mut self, parent: &str, methods: Vec<&Method>) {
+        for method in methods {
+            self.check_callable(
+                &format!("{parent}::{}", method.name()),
+                method.arguments(),
+                method.return_type(),
+                method.throws_type(),
+            );
+        }
+    }
+}
+
+/// Walk `ci` and list everything the Dart backend would fail to generate
+pub fn check_component(ci: &ComponentInterface) -> Vec<UnsupportedItem> {
+    let mut checker = Checker::default();
+
+    for func in ci.function_definitions() {
+        checker.check_callable(
+            &format!("{}::{}", ci.crate_name(), func.name()),
+            func.arguments(),
+            func.return_type(),
+            func.throws_type(),
+        );
+    }
+
+    for obj in ci.object_definitions() {
+        let path = type_path(&obj.as_type());
+        if let Some(reason) = unsupported_reason(&obj.as_type()) {
+            checker.report(path.clone(), reason);
+            continue;
+        }
+        for cons in obj.constructors() {
+            checker.check_callable(
+                &format!("{path}::{}", cons.name()),
+                cons.arguments(),
+                None,
+                cons.throws_type(),
+            );
+        }
+        checker.check_methods(&path, obj.methods());
+    }
+
+    for cbi in ci.callback_interface_definitions() {
+        checker.check_methods(&type_path(&cbi.as_type()), cbi.methods());
+    }
+
+    for rec in ci.record_definitions() {
+        let path = type_path(&rec.as_type());
+        for field in rec.fields() {
+            if let Some(reason) = unsupported_reason(&field.as_type()) {
+                checker.report(format!("{path}::{}", field.name()), reason);
+            }
+        }
+    }
+
+    for enum_ in ci.enum_definitions() {
+        let path = type_path(&enum_.as_type());
+        for variant in enum_.variants() {
+            for field in variant.fields() {
+                checker.check_type(
+                    &format!("{path}::{}", variant.name()),
+                    &format!("field `{}`", field.name()),
+                    &field.as_type(),
+                );
+            }
+        }
+    }
+
+    checker.items
+}
+
+/// List the callback interface methods `callback_mode = "listener"` can't bind: a listener
+/// returns to Rust before Dart runs, leaving no way to report a value or an error back
+pub(super) fn check_listener_callbacks(ci: &ComponentInterface) -> Vec<UnsupportedItem> {
+    let mut checker = Checker::default();
+    for cbi in ci.callback_interface_definitions() {
+        let path = type_path(&cbi.as_type());
+        for method in cbi.methods() {
+            let reason = match (method.return_type(), method.throws_type()) {
+                (None, None) => continue,
+                (Some(_), _) => "listener callbacks can't return a value to Rust",
+                (None, Some(_)) => "listener callbacks can't report an error to Rust",
+            };
+            checker.report(format!("{path}::{}", method.name()), reason.to_string());
+        }
+    }
+    checker.items
+}
+
+/// Check the component defined by `udl_file`, extended with the proc-macro metadata of `library_file`
+pub fn check_udl(
+    udl_file: &Utf8Path,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+) -> Result<Vec<UnsupportedItem>> {
+    let ci = component_from_udl(udl_file, library_file, crate_name)?;
+    Ok(check_component(&ci))
+}
+
+/// Check every component found in `library_file`, or only `crate_name` when given
+pub fn check_library(
+    library_file: &Utf8Path,
+    crate_name: Option<&str>,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<Vec<UnsupportedItem>> {
+    Ok(
+        components_from_library(library_file, crate_name, config_supplier)?
+            .iter()
+            .flat_map(check_component)
+            .collect(),
+    )
+}
@@ -0,0 +1,76 @@
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use serde::Deserialize;
+use uniffi_bindgen::{BindgenCrateConfigSupplier, ComponentInterface};
+
+/// Load the component defined by `udl_file`, extended with the proc-macro metadata of `library_file`
+pub fn component_from_udl(
+    udl_file: &Utf8Path,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+) -> Result<ComponentInterface> {
+    let crate_name = match crate_name {
+        Some(crate_name) => crate_name.to_string(),
+        None => crate_name_from_cargo_toml(udl_file)?,
+    };
+    let udl = std::fs::read_to_string(udl_file)
+        .with_context(|| format!("Failed to read UDL from {udl_file}"))?;
+    let mut ci = ComponentInterface::from_webidl(&udl, &crate_name)
+        .with_context(|| format!("Failed to parse UDL {udl_file}"))?;
+    if let Some(library_file) = library_file {
+        uniffi_bindgen::macro_metadata::add_to_ci_from_library(&mut ci, library_file)?;
+    }
+    Ok(ci)
+}
+
+/// Load every component found in `library_file`, or only `crate_name` when given
+pub fn components_from_library(
+    library_file: &Utf8Path,
+    crate_name: Option<&str>,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+) -> Result<Vec<ComponentInterface>> {
+    let components: Vec<_> = uniffi_bindgen::find_components(library_file, config_supplier)
+        .with_context(|| format!("finding components in '{library_file}'"))?
+        .into_iter()
+        .map(|component| component.ci)
+        .filter(|ci| crate_name.is_none_or(|name| name == ci.crate_name()))
+        .collect();
+    if let (Some(crate_name), true) = (crate_name, components.is_empty()) {
+        anyhow::bail!("Crate {crate_name} not found in {library_file}");
+    }
+    Ok(components)
+}
+
+/// The crate the UDL belongs to, read from the `Cargo.toml` next to its `src` directory
+fn crate_name_from_cargo_toml(udl_file: &Utf8Path) -> Result<String> {
+    #[derive(Deserialize)]
+    struct CargoToml {
+        package: Package,
+        #[serde(default)]
+        lib: Lib,
+    }
+
+    #[derive(Deserialize)]
+    struct Package {
+        name: String,
+    }
+
+    #[derive(Default, Deserialize)]
+    struct Lib {
+        name: Option<String>,
+    }
+
+    let cargo_toml = udl_file
+        .parent()
+        .and_then(|src| src.parent())
+        .context("UDL file is expected in the crate's `src` directory")?
+        .join("Cargo.toml");
+    let cargo_toml: CargoToml = toml::from_str(
+        &std::fs::read_to_string(&cargo_toml)
+            .context("Can't find Cargo.toml to determine the crate name")?,
+    )?;
+    Ok(cargo_toml
+        .lib
+        .name
+        .unwrap_or_else(|| cargo_toml.package.name.replace('-', "_")))
+}
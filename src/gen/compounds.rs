@@ -61,7 +61,7 @@ macro_rules! impl_renderable_for_compound {
                         class $cl_name {
 
                             static $type_label lift( RustBuffer buf) {
-                                return $cl_name.read(buf.asUint8List()).value;
+                                return buf.consume((bytes) => $cl_name.read(bytes).value);
                             }
 
                             static LiftRetVal<$type_label> read( Uint8List buf) {
@@ -81,21 +81,7 @@ macro_rules! impl_renderable_for_compound {
                             }
 
                             static RustBuffer lower( $type_label value) {
-                                if (value == null) {
-                                    return toRustBuffer(Uint8List.fromList([0]));
-                                }
-
-                                final length = $cl_name.allocationSize(value);
-
-                                final Pointer<Uint8> frameData = calloc<Uint8>(length); // Allocate a pointer large enough.
-                                final buf = frameData.asTypedList(length); // Create a list that uses our pointer to copy in the data.
-
-                                $cl_name.write(value, buf);
-
-                                final bytes = calloc<ForeignBytes>();
-                                bytes.ref.len = length;
-                                bytes.ref.data = frameData;
-                                return RustBuffer.fromBytes(bytes.ref);
+                                return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                             }
 
                             static int write( $type_label value, Uint8List buf) {
@@ -138,7 +124,7 @@ macro_rules! impl_renderable_for_compound {
                         class $cl_name {
 
                             static $type_label lift( RustBuffer buf) {
-                                return $cl_name.read(buf.asUint8List()).value;
+                                return buf.consume((bytes) => $cl_name.read(bytes).value);
                             }
 
                             static LiftRetVal<$type_label> read( Uint8List buf) {
@@ -166,9 +152,7 @@ macro_rules! impl_renderable_for_compound {
                             }
 
                             static RustBuffer lower( $type_label value) {
-                                final buf = Uint8List(allocationSize(value));
-                                write(value, buf);
-                                return toRustBuffer(buf);
+                                return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                             }
                         }
                     }
@@ -246,7 +230,7 @@ impl Renderable for MapCodeType {
         quote! {
             class $cl_name {
                 static Map<$key_type_label, $val_type_label> lift(RustBuffer buf) {
-                    return $cl_name.read(buf.asUint8List()).value;
+                    return buf.consume((bytes) => $cl_name.read(bytes).value);
                 }
 
                 static LiftRetVal<Map<$key_type_label, $val_type_label>> read(Uint8List buf) {
@@ -280,9 +264,7 @@ impl Renderable for MapCodeType {
                 }
 
                 static RustBuffer lower(Map<$key_type_label, $val_type_label> value) {
-                    final buf = Uint8List(allocationSize(value));
-                    write(value, buf);
-                    return toRustBuffer(buf);
+                    return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                 }
             }
         }
@@ -3,7 +3,7 @@ use super::render::{Renderable, TypeHelperRenderer};
 use super::CodeType;
 use genco::prelude::*;
 use uniffi_bindgen::backend::Type;
-use uniffi_bindgen::interface::AsType;
+use uniffi_bindgen::interface::{AsType, FfiType};
 
 #[derive(Debug)]
 pub struct CustomCodeType {
@@ -38,6 +38,50 @@ impl AsType for CustomCodeType {
     }
 }
 
+impl CustomCodeType {
+    /// Converter going through the builtin, with `lift` and `lower` converting from and to it
+    fn generate_converter(
+        &self,
+        type_helper: &dyn TypeHelperRenderer,
+        lift: impl Fn(&str) -> String,
+        lower: impl Fn(&str) -> String,
+    ) -> dart::Tokens {
+        let ffi_converter_name = &self.ffi_converter_name();
+        let type_name = &self.type_label();
+        let builtin_ffi_converter_name = &(*self.builtin).as_codetype().ffi_converter_name();
+        let ffi_type = &DartCodeOracle::ffi_dart_type_label(
+            Some(&FfiType::from(&*self.builtin)),
+            type_helper.get_ci(),
+        );
+        let lowered = &lower("value");
+
+        quote! {
+            class $(ffi_converter_name) {
+                static $(type_name) lift($ffi_type value) {
+                    return $(lift(&format!("{builtin_ffi_converter_name}.lift(value)")));
+                }
+
+                static $ffi_type lower($(type_name) value) {
+                    return $(builtin_ffi_converter_name).lower($(lowered));
+                }
+
+                static LiftRetVal<$(type_name)> read(Uint8List buf) {
+                    final builtin = $(builtin_ffi_converter_name).read(buf);
+                    return LiftRetVal($(lift("builtin.value")), builtin.bytesRead);
+                }
+
+                static int allocationSize($(type_name) value) {
+                    return $(builtin_ffi_converter_name).allocationSize($(lowered));
+                }
+
+                static int write($(type_name) value, Uint8List buf) {
+                    return $(builtin_ffi_converter_name).write($(lowered), buf);
+                }
+            }
+        }
+    }
+}
+
 impl Renderable for CustomCodeType {
     fn render_type_helper(&self, type_helper: &dyn TypeHelperRenderer) -> dart::Tokens {
         type_helper.include_once_check(&self.name, &self.as_type());
@@ -46,6 +90,54 @@ impl Renderable for CustomCodeType {
         let type_name = &self.type_label();
         let builtin_ffi_converter_name = &(*self.builtin).as_codetype().ffi_converter_name();
         let builtin_name = DartCodeOracle::dart_type_label(Some(&*self.builtin));
+        let config = type_helper.get_config();
+
+        if let Some(custom_type) = config.custom_type(&self.name) {
+            let dart_type = match custom_type.type_name() {
+                Some(dart_type) => quote!($dart_type),
+                None => builtin_name,
+            };
+            let converter = self.generate_converter(
+                type_helper,
+                |builtin| custom_type.lift(builtin),
+                |custom| custom_type.lower(custom),
+            );
+
+            return quote! {
+                typedef $(type_name) = $(dart_type);
+
+                $converter
+            };
+        }
+
+        if config.wrap_custom_types() {
+            // A distinct class, so Dart rejects a bare builtin where the custom type is expected
+            let converter = self.generate_converter(
+                type_helper,
+                |builtin| format!("{type_name}({builtin})"),
+                |custom| format!("{custom}.value"),
+            );
+
+            return quote! {
+                class $(type_name) {
+                    final $(&builtin_name) value;
+
+                    const $(type_name)(this.value);
+
+                    @override
+                    bool operator ==(Object other) =>
+                        identical(this, other) || other is $(type_name) && other.value == value;
+
+                    @override
+                    int get hashCode => value.hashCode;
+
+                    @override
+                    String toString() => $(format!("\"{type_name}($value)\""));
+                }
+
+                $converter
+            };
+        }
 
         quote! {
             typedef $(type_name) = $(builtin_name);
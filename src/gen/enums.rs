@@ -61,15 +61,18 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
     let ffi_converter_name = &obj.as_codetype().ffi_converter_name();
     if obj.is_flat() {
         quote! {
+            $(DartCodeOracle::doc_comment(obj.docstring()))
             enum $dart_cls_name {
-                $(for variant in obj.variants() =>
-                $(DartCodeOracle::enum_variant_name(variant.name())),)
+                $(for variant in obj.variants() {
+                    $(DartCodeOracle::doc_comment(variant.docstring()))
+                    $(DartCodeOracle::enum_variant_name(variant.name())),
+                })
                 ;
             }
 
             class $ffi_converter_name {
                 static $dart_cls_name lift( RustBuffer buffer) {
-                    final index = buffer.asUint8List().buffer.asByteData().getInt32(0);
+                    final index = buffer.consume((bytes) => bytes.buffer.asByteData(bytes.offsetInBytes).getInt32(0));
                     switch(index) {
                         $(for (index, variant) in obj.variants().iter().enumerate() =>
                         case $(index + 1):
@@ -81,7 +84,7 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
                 }
 
                 static RustBuffer lower( $dart_cls_name input) {
-                    return toRustBuffer(createUint8ListFromInt(input.index + 1));
+                    return RustBuffer.allocWith(4, (bytes) => bytes.buffer.asByteData(bytes.offsetInBytes).setInt32(0, input.index + 1));
                 }
             }
         }
@@ -156,10 +159,9 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
             // Pre-process field reading code
             let field_read_code: Vec<dart::Tokens> = variant_obj.fields().iter().enumerate().map(|(i, field)| {
                 if is_flat_enum(field, type_helper) {
-                    // Handle flat enums specially - they serialize as int32 (4 bytes)
+                    // Handle flat enums specially - they serialize as their 1-based index in an int32
                     quote!(
-                        final $(field_name(field, i))_int = buf.buffer.asByteData(new_offset).getInt32(0);
-                        final $(field_name(field, i)) = $(field_ffi_converter_name(field)).lift(toRustBuffer(createUint8ListFromInt($(field_name(field, i))_int)));
+                        final $(field_name(field, i)) = $(field_type(field, type_helper)).values[buf.buffer.asByteData(new_offset).getInt32(0) - 1];
                         new_offset += 4;
                     )
                 } else {
@@ -183,11 +185,9 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
             // Pre-process field write code
             let field_write_code: Vec<dart::Tokens> = variant_obj.fields().iter().enumerate().map(|(i, field)| {
                 if is_flat_enum(field, type_helper) {
-                    // Handle flat enums specially - lower to RustBuffer and extract int32
+                    // Handle flat enums specially - write their 1-based index as an int32
                     quote!(
-                        final $(field_name(field, i))_buffer = $(field_ffi_converter_name(field)).lower($(field_name(field, i)));
-                        final $(field_name(field, i))_int = $(field_name(field, i))_buffer.asUint8List().buffer.asByteData().getInt32(0);
-                        buf.buffer.asByteData(new_offset).setInt32(0, $(field_name(field, i))_int);
+                        buf.buffer.asByteData(new_offset).setInt32(0, $(field_name(field, i)).index + 1);
                         new_offset += 4;
                     )
                 } else {
@@ -229,8 +229,12 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
                 };
 
             variants.push(quote!{
+                $(DartCodeOracle::doc_comment(variant_obj.docstring()))
                 class $variant_dart_cls_name extends $dart_cls_name {
-                    $(for (i, field) in variant_obj.fields().iter().enumerate() => final $(field_type(field, type_helper)) $(field_name(field, i));  )
+                    $(for (i, field) in variant_obj.fields().iter().enumerate() {
+                        $(DartCodeOracle::doc_comment(field.docstring()))
+                        final $(field_type(field, type_helper)) $(field_name(field, i));
+                    })
 
                     // Add the public const constructor
                     $variant_dart_cls_name($constructor_param_list);
@@ -249,9 +253,7 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
 
                     @override
                     RustBuffer lower() {
-                        final buf = Uint8List(allocationSize());
-                        write(buf);
-                        return toRustBuffer(buf);
+                        return RustBuffer.allocWith(allocationSize(), write);
                     }
 
                     @override
@@ -300,6 +302,7 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
         };
 
         quote! {
+            $(DartCodeOracle::doc_comment(obj.docstring()))
             abstract class $dart_cls_name $implements_exception {
                 RustBuffer lower();
                 int allocationSize();
@@ -308,7 +311,7 @@ pub fn generate_enum(obj: &Enum, type_helper: &dyn TypeHelperRenderer) -> dart::
 
             class $ffi_converter_name {
                 static $dart_cls_name lift( RustBuffer buffer) {
-                    return $ffi_converter_name.read(buffer.asUint8List()).value;
+                    return buffer.consume((bytes) => $ffi_converter_name.read(bytes).value);
                 }
 
                 static LiftRetVal<$dart_cls_name> read( Uint8List buf) {
@@ -0,0 +1,152 @@
+use anyhow::{bail, Result};
+use camino::Utf8Path;
+use genco::lang::dart;
+use genco::prelude::*;
+use uniffi_bindgen::ComponentInterface;
+
+use super::{BindingMode, Config};
+
+/// Symbol of one of the finalizer functions of the crate `crate_name`
+fn symbol(crate_name: &str, name: &str) -> String {
+    format!("uniffi_dart_{crate_name}_{name}")
+}
+
+/// Whether the bindings of `ci` free objects, through the finalizer functions of its crate
+pub(crate) fn has_objects(ci: &ComponentInterface) -> bool {
+    ci.object_definitions()
+        .iter()
+        .any(|obj| !obj.has_callback_interface())
+}
+
+/// Rust source appended to the scaffolding of the crate `crate_name`: the finalizer functions
+/// `uniffi_dart::setup_scaffolding!()` defines for proc-macro crates
+#[cfg(feature = "build")]
+pub(crate) fn rust_scaffolding(crate_name: &str) -> String {
+    let token = symbol(crate_name, "finalizer_token");
+    let finalize = symbol(crate_name, "finalize");
+    let token_free = symbol(crate_name, "finalizer_token_free");
+    format!(
+        r#"
+#[doc(hidden)]
+mod uniffi_dart_finalizer {{
+    type FreeFn =
+        unsafe extern "C" fn(*const ::std::ffi::c_void, &mut ::uniffi::RustCallStatus);
+
+    struct Token {{
+        ptr: *const ::std::ffi::c_void,
+        free: FreeFn,
+    }}
+
+    #[no_mangle]
+    extern "C" fn {token}(
+        ptr: *const ::std::ffi::c_void,
+        free: FreeFn,
+    ) -> *mut ::std::ffi::c_void {{
+        ::std::boxed::Box::into_raw(::std::boxed::Box::new(Token {{ ptr, free }})).cast()
+    }}
+
+    #[no_mangle]
+    unsafe extern "C" fn {finalize}(token: *mut ::std::ffi::c_void) {{
+        let token = ::std::boxed::Box::from_raw(token.cast::<Token>());
+        let mut status = ::uniffi::RustCallStatus::default();
+        (token.free)(token.ptr, &mut status);
+        // Nobody is left to report a panicking `Drop` to
+        if status.code != ::uniffi::RustCallStatusCode::Success {{
+            ::std::mem::ManuallyDrop::into_inner(status.error_buf).destroy();
+        }}
+    }}
+
+    #[no_mangle]
+    unsafe extern "C" fn {token_free}(token: *mut ::std::ffi::c_void) {{
+        ::std::mem::drop(::std::boxed::Box::from_raw(token.cast::<Token>()));
+    }}
+}}
+"#
+    )
+}
+
+/// `_uniffiObjectFinalizer`, freeing the objects of every class through `finalize`, and the
+/// top-level `@Native` externals of the finalizer functions in [`BindingMode::Native`]
+pub(crate) fn generate_object_finalizer(ci: &ComponentInterface, config: &Config) -> dart::Tokens {
+    let crate_name = ci.crate_name();
+    let finalize = &symbol(crate_name, "finalize");
+    if config.binding_mode() == BindingMode::Lookup {
+        return quote! {
+            final _uniffiObjectFinalizer = NativeFinalizer(
+                _UniffiLib._dylib.lookup<NativeFunction<Void Function(Pointer<Void>)>>($(quoted(finalize))));
+        };
+    }
+
+    let asset_id = config
+        .asset_id()
+        .map(|asset_id| quote!(, assetId: $(quoted(asset_id))));
+    let token = &symbol(crate_name, "finalizer_token");
+    let token_free = &symbol(crate_name, "finalizer_token_free");
+    quote! {
+        final _uniffiObjectFinalizer = NativeFinalizer(
+            Native.addressOf<NativeFunction<Void Function(Pointer<Void>)>>($(format!("_{finalize}"))));
+
+        @Native<Void Function(Pointer<Void>)>(symbol: $(quoted(finalize))$(asset_id.clone()))
+        external void $(format!("_{finalize}"))(Pointer<Void> token);
+
+        @Native<Pointer<Void> Function(Pointer<Void>, Pointer<Void>)>(symbol: $(quoted(token))$(asset_id.clone()))
+        external Pointer<Void> $(format!("_{token}"))(Pointer<Void> ptr, Pointer<Void> free);
+
+        @Native<Void Function(Pointer<Void>)>(symbol: $(quoted(token_free))$(asset_id))
+        external void $(format!("_{token_free}"))(Pointer<Void> token);
+    }
+}
+
+/// Members of `_UniffiLib` creating and freeing finalizer tokens: `finalizerToken(ptr, free)`
+/// and `finalizerTokenFree(token)`
+pub(crate) fn uniffi_finalizer_definitions(
+    ci: &ComponentInterface,
+    config: &Config,
+) -> dart::Tokens {
+    let crate_name = ci.crate_name();
+    let token = &symbol(crate_name, "finalizer_token");
+    let token_free = &symbol(crate_name, "finalizer_token_free");
+    if config.binding_mode() == BindingMode::Native {
+        return quote! {
+            Pointer<Void> finalizerToken(Pointer<Void> ptr, Pointer<Void> free) =>
+                $(format!("_{token}"))(ptr, free);
+            void finalizerTokenFree(Pointer<Void> token) => $(format!("_{token_free}"))(token);
+        };
+    }
+    quote! {
+        late final Pointer<Void> Function(Pointer<Void>, Pointer<Void>) finalizerToken = _dylib.lookupFunction<
+            Pointer<Void> Function(Pointer<Void>, Pointer<Void>),
+            Pointer<Void> Function(Pointer<Void>, Pointer<Void>)
+        >($(quoted(token)));
+        late final void Function(Pointer<Void>) finalizerTokenFree = _dylib.lookupFunction<
+            Void Function(Pointer<Void>),
+            void Function(Pointer<Void>)
+        >($(quoted(token_free)));
+    }
+}
+
+/// Fail unless `library_file` exports the finalizer functions of every component with objects.
+///
+/// Symbol names are stored NUL-terminated in the symbol and export tables of every library
+/// format, prefixed with `_` on Apple platforms, so they are looked up in the raw bytes.
+pub(crate) fn check_library<'a>(
+    library_file: &Utf8Path,
+    components: impl IntoIterator<Item = &'a ComponentInterface>,
+) -> Result<()> {
+    let library = std::fs::read(library_file)?;
+    for ci in components.into_iter().filter(|ci| has_objects(ci)) {
+        let finalize = format!("{}\0", symbol(ci.crate_name(), "finalize"));
+        if !library
+            .windows(finalize.len())
+            .any(|bytes| bytes == finalize.as_bytes())
+        {
+            bail!(
+                "{library_file} lacks the finalizer functions freeing the objects of `{crate_name}`: \
+                 build the crate with `uniffi_dart::generate_scaffolding` from its build script, \
+                 or call `uniffi_dart::setup_scaffolding!()` in it",
+                crate_name = ci.crate_name()
+            );
+        }
+    }
+    Ok(())
+}
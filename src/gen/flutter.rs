@@ -0,0 +1,237 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uniffi_bindgen::{ComponentInterface, GenerationSettings};
+
+use super::Config;
+
+/// Builds the crate with cargo, for the host on Linux and for `ANDROID_ABI` on Android
+const RUST_CMAKE: &str = r#"# Builds the @CRATE@ crate with cargo, setting RUST_LIBRARY to the resulting library
+set(CRATE_MANIFEST "${CMAKE_CURRENT_LIST_DIR}/../@MANIFEST_PATH@")
+set(CARGO_TARGET_DIR "${CMAKE_CURRENT_BINARY_DIR}/cargo")
+
+if(ANDROID)
+  if(ANDROID_ABI STREQUAL "arm64-v8a")
+    set(RUST_TARGET "aarch64-linux-android")
+    set(CLANG_TARGET "aarch64-linux-android")
+  elseif(ANDROID_ABI STREQUAL "armeabi-v7a")
+    set(RUST_TARGET "armv7-linux-androideabi")
+    set(CLANG_TARGET "armv7a-linux-androideabi")
+  elseif(ANDROID_ABI STREQUAL "x86_64")
+    set(RUST_TARGET "x86_64-linux-android")
+    set(CLANG_TARGET "x86_64-linux-android")
+  elseif(ANDROID_ABI STREQUAL "x86")
+    set(RUST_TARGET "i686-linux-android")
+    set(CLANG_TARGET "i686-linux-android")
+  else()
+    message(FATAL_ERROR "Unsupported Android ABI ${ANDROID_ABI}")
+  endif()
+  string(TOUPPER "${RUST_TARGET}" LINKER_VARIABLE)
+  string(REPLACE "-" "_" LINKER_VARIABLE "CARGO_TARGET_${LINKER_VARIABLE}_LINKER")
+  set(CARGO_ENV "${LINKER_VARIABLE}=${ANDROID_TOOLCHAIN_ROOT}/bin/${CLANG_TARGET}${ANDROID_PLATFORM_LEVEL}-clang")
+  set(CARGO_TARGET_ARGS --target "${RUST_TARGET}")
+  set(RUST_LIBRARY "${CARGO_TARGET_DIR}/${RUST_TARGET}/release/lib@LIBRARY@.so")
+else()
+  set(CARGO_ENV "")
+  set(CARGO_TARGET_ARGS "")
+  set(RUST_LIBRARY "${CARGO_TARGET_DIR}/release/lib@LIBRARY@.so")
+endif()
+
+add_custom_target(@PACKAGE@_rust ALL
+  COMMAND "${CMAKE_COMMAND}" -E env ${CARGO_ENV}
+    cargo build --release --lib --manifest-path "${CRATE_MANIFEST}" --target-dir "${CARGO_TARGET_DIR}" ${CARGO_TARGET_ARGS}
+  BYPRODUCTS "${RUST_LIBRARY}"
+  COMMENT "Building the @CRATE@ crate with cargo"
+  VERBATIM)
+"#;
+
+/// Entry point of the Android build, packaging the library with the plugin
+const ANDROID_CMAKE_LISTS: &str = r#"cmake_minimum_required(VERSION 3.10)
+project(@PACKAGE@ LANGUAGES C)
+
+include("${CMAKE_CURRENT_SOURCE_DIR}/rust.cmake")
+
+add_custom_command(TARGET @PACKAGE@_rust POST_BUILD
+  COMMAND "${CMAKE_COMMAND}" -E copy "${RUST_LIBRARY}" "${CMAKE_LIBRARY_OUTPUT_DIRECTORY}"
+  VERBATIM)
+"#;
+
+/// Entry point of the Linux build, bundling the library with the app
+const LINUX_CMAKE_LISTS: &str = r#"cmake_minimum_required(VERSION 3.10)
+set(PROJECT_NAME "@PACKAGE@")
+project(${PROJECT_NAME} LANGUAGES C)
+
+include("${CMAKE_CURRENT_SOURCE_DIR}/../src/rust.cmake")
+
+set(@PACKAGE@_bundled_libraries
+  "${RUST_LIBRARY}"
+  PARENT_SCOPE
+)
+"#;
+
+const ANDROID_BUILD_GRADLE: &str = r#"group = "uniffi.@PACKAGE@"
+version = "@VERSION@"
+
+buildscript {
+    repositories {
+        google()
+        mavenCentral()
+    }
+
+    dependencies {
+        classpath("com.android.tools.build:gradle:8.1.0")
+    }
+}
+
+rootProject.allprojects {
+    repositories {
+        google()
+        mavenCentral()
+    }
+}
+
+apply plugin: "com.android.library"
+
+android {
+    namespace = "uniffi.@PACKAGE@"
+    compileSdk = 34
+    ndkVersion = android.ndkVersion
+
+    externalNativeBuild {
+        cmake {
+            path = "../src/CMakeLists.txt"
+        }
+    }
+
+    defaultConfig {
+        minSdk = 21
+    }
+}
+"#;
+
+const ANDROID_SETTINGS_GRADLE: &str = "rootProject.name = \"@PACKAGE@\"\n";
+
+/// Builds the crate as a static library for the platform and architectures Xcode builds
+const APPLE_BUILD_SCRIPT: &str = r#"#!/bin/sh
+# Builds the @CRATE@ crate with cargo into "$BUILT_PRODUCTS_DIR/lib@LIBRARY@.a"
+set -e
+
+export PATH="$HOME/.cargo/bin:$PATH"
+MANIFEST="$PODS_TARGET_SRCROOT/../@MANIFEST_PATH@"
+TARGET_DIR="$CONFIGURATION_TEMP_DIR/cargo"
+
+LIBRARIES=""
+for ARCH in $ARCHS; do
+  case "$PLATFORM_NAME/$ARCH" in
+    iphoneos/arm64) TARGET=aarch64-apple-ios ;;
+    iphonesimulator/arm64) TARGET=aarch64-apple-ios-sim ;;
+    iphonesimulator/x86_64) TARGET=x86_64-apple-ios ;;
+    macosx/arm64) TARGET=aarch64-apple-darwin ;;
+    macosx/x86_64) TARGET=x86_64-apple-darwin ;;
+    *) echo "error: no Rust target for $PLATFORM_NAME/$ARCH" >&2; exit 1 ;;
+  esac
+  cargo build --release --lib --manifest-path "$MANIFEST" --target "$TARGET" --target-dir "$TARGET_DIR"
+  LIBRARIES="$LIBRARIES $TARGET_DIR/$TARGET/release/lib@LIBRARY@.a"
+done
+
+lipo -create $LIBRARIES -output "$BUILT_PRODUCTS_DIR/lib@LIBRARY@.a"
+"#;
+
+const PODSPEC: &str = r#"Pod::Spec.new do |s|
+  s.name             = '@PACKAGE@'
+  s.version          = '@VERSION@'
+  s.summary          = 'Dart bindings for the @CRATE@ Rust crate'
+  s.homepage         = 'http://example.com'
+  s.license          = { :file => '../LICENSE' }
+  s.author           = { 'Your Company' => 'email@example.com' }
+  s.source           = { :path => '.' }
+  s.source_files     = 'Classes/**/*'
+  s.dependency '@FLUTTER_POD@'
+  s.platform = @PLATFORM@
+
+  s.script_phase = {
+    :name => 'Build the @CRATE@ crate',
+    :script => 'sh "$PODS_TARGET_SRCROOT/../src/build_apple.sh"',
+    :execution_position => :before_compile,
+    :output_files => ['${BUILT_PRODUCTS_DIR}/lib@LIBRARY@.a'],
+  }
+  # Linked statically, the bindings find the symbols in the process
+  s.pod_target_xcconfig = {
+    'DEFINES_MODULE' => 'YES',
+    'OTHER_LDFLAGS' => '-force_load ${BUILT_PRODUCTS_DIR}/lib@LIBRARY@.a',
+  }
+end
+"#;
+
+const POD_SOURCE: &str =
+    "// CocoaPods only runs the pod's script phase, building the crate, for pods with sources\n";
+
+/// Build settings of the Flutter plugin, configured under `[bindings.dart.flutter_plugin]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlutterPluginConfig {
+    /// The crate's `Cargo.toml`, relative to the plugin root
+    manifest_path: String,
+}
+
+impl FlutterPluginConfig {
+    pub fn manifest_path(&self) -> &str {
+        &self.manifest_path
+    }
+}
+
+/// Write the platform build files of a Flutter FFI plugin to the output directory: CMake for
+/// Android and Linux, and podspecs for iOS and macOS, each building the crate with cargo
+pub(super) fn write_plugin_platforms(
+    settings: &GenerationSettings,
+    ci: &ComponentInterface,
+    config: &Config,
+) -> Result<()> {
+    let plugin = config.flutter_plugin().with_context(|| {
+        format!(
+            "{}: `output_mode = \"flutter_plugin\"` requires a `[bindings.dart.flutter_plugin]` table",
+            ci.crate_name()
+        )
+    })?;
+    let package = config.dart_package_name(ci);
+    let fill = |template: &str| {
+        template
+            .replace("@PACKAGE@", &package)
+            .replace("@VERSION@", config.package_version())
+            .replace("@CRATE@", ci.crate_name())
+            .replace("@LIBRARY@", &config.cdylib_name())
+            .replace("@MANIFEST_PATH@", plugin.manifest_path())
+    };
+
+    let mut files = vec![
+        ("src/rust.cmake".to_string(), fill(RUST_CMAKE)),
+        ("src/CMakeLists.txt".to_string(), fill(ANDROID_CMAKE_LISTS)),
+        ("src/build_apple.sh".to_string(), fill(APPLE_BUILD_SCRIPT)),
+        ("linux/CMakeLists.txt".to_string(), fill(LINUX_CMAKE_LISTS)),
+        (
+            "android/build.gradle".to_string(),
+            fill(ANDROID_BUILD_GRADLE),
+        ),
+        (
+            "android/settings.gradle".to_string(),
+            fill(ANDROID_SETTINGS_GRADLE),
+        ),
+    ];
+    for (dir, flutter_pod, platform) in [
+        ("ios", "Flutter", ":ios, '12.0'"),
+        ("macos", "FlutterMacOS", ":osx, '10.14'"),
+    ] {
+        let podspec = fill(PODSPEC)
+            .replace("@FLUTTER_POD@", flutter_pod)
+            .replace("@PLATFORM@", platform);
+        files.push((format!("{dir}/{package}.podspec"), podspec));
+        files.push((format!("{dir}/Classes/{package}.c"), POD_SOURCE.to_string()));
+    }
+
+    for (path, contents) in files {
+        let path = settings.out_dir.join(path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
@@ -30,8 +30,10 @@ pub fn generate_function(func: &Function, type_helper: &dyn TypeHelperRenderer)
         quote!(null)
     };
 
+    let doc = DartCodeOracle::doc_comment(func.docstring());
+
     // Use centralized callback-aware argument lowering
-    if func.is_async() {
+    let body = if func.is_async() {
         quote!(
             Future<$ret> $(DartCodeOracle::fn_name(func.name()))($args) {
                 return uniffiRustCallAsync(
@@ -64,5 +66,10 @@ pub fn generate_function(func: &Function, type_helper: &dyn TypeHelperRenderer)
                 )), $error_handler);
             }
         )
+    };
+
+    quote! {
+        $doc
+        $body
     }
 }
@@ -0,0 +1,200 @@
+use anyhow::{bail, Result};
+use genco::lang::dart;
+use genco::prelude::*;
+use serde::{Deserialize, Serialize};
+use uniffi_bindgen::{ComponentInterface, GenerationSettings};
+
+use super::{write_dart_file, BindingMode, Config};
+
+/// Versions of the packages the generated build hook depends on
+const HOOK_DEPENDENCIES: [(&str, &str); 2] = [("hooks", "^1.0.0"), ("code_assets", "^1.0.0")];
+
+/// Rust target triples per Dart target OS and architecture, the iOS simulator aside
+const RUST_TARGETS: [(&str, &str, &str); 14] = [
+    ("android", "arm", "armv7-linux-androideabi"),
+    ("android", "arm64", "aarch64-linux-android"),
+    ("android", "ia32", "i686-linux-android"),
+    ("android", "x64", "x86_64-linux-android"),
+    ("iOS", "arm64", "aarch64-apple-ios"),
+    ("iOS", "x64", "x86_64-apple-ios"),
+    ("linux", "arm", "armv7-unknown-linux-gnueabihf"),
+    ("linux", "arm64", "aarch64-unknown-linux-gnu"),
+    ("linux", "riscv64", "riscv64gc-unknown-linux-gnu"),
+    ("linux", "x64", "x86_64-unknown-linux-gnu"),
+    ("macOS", "arm64", "aarch64-apple-darwin"),
+    ("macOS", "x64", "x86_64-apple-darwin"),
+    ("windows", "arm64", "aarch64-pc-windows-msvc"),
+    ("windows", "x64", "x86_64-pc-windows-msvc"),
+];
+
+/// Native-assets build hook compiling the crate, configured under `[bindings.dart.build_hook]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildHookConfig {
+    /// The crate's `Cargo.toml`, relative to the Dart package root
+    manifest_path: String,
+    /// The Dart package root, relative to the output directory
+    package_root: Option<String>,
+}
+
+impl BuildHookConfig {
+    pub fn manifest_path(&self) -> &str {
+        &self.manifest_path
+    }
+
+    pub fn package_root(&self) -> &str {
+        self.package_root.as_deref().unwrap_or(".")
+    }
+}
+
+/// `hook/build.dart`, building the cdylib with cargo and registering it as the code asset the
+/// `@Native` bindings resolve against
+fn generate_build_hook(
+    ci: &ComponentInterface,
+    config: &Config,
+    hook: &BuildHookConfig,
+) -> Result<dart::Tokens> {
+    if config.binding_mode() != BindingMode::Native {
+        bail!(
+            "{}: `build_hook` requires `binding_mode = \"native\"`",
+            ci.crate_name()
+        );
+    }
+    let Some(asset_name) = config
+        .asset_id()
+        .and_then(|asset_id| asset_id.strip_prefix("package:"))
+        .and_then(|path| path.split_once('/'))
+        .map(|(_, name)| name)
+    else {
+        bail!(
+            "{}: `build_hook` requires an `asset_id` of the form `package:<package>/<name>`",
+            ci.crate_name()
+        );
+    };
+
+    Ok(quote! {
+        import "dart:io";
+
+        import "package:code_assets/code_assets.dart";
+        import "package:hooks/hooks.dart";
+
+        const _manifestPath = $(quoted(hook.manifest_path()));
+        const _libraryName = $(quoted(config.cdylib_name()));
+        const _assetName = $(quoted(asset_name));
+
+        final _rustTargets = {
+            $(for (os, arch, target) in RUST_TARGETS join ($['\r']) =>
+                $(format!("(OS.{os}, Architecture.{arch})")): $(quoted(target)),
+            )
+        };
+
+        String _rustTarget(CodeConfig code) {
+            if (code.targetOS == OS.iOS && code.iOS.targetSdk == IOSSdk.iPhoneSimulator) {
+                if (code.targetArchitecture == Architecture.arm64) return "aarch64-apple-ios-sim";
+            }
+            final target = _rustTargets[(code.targetOS, code.targetArchitecture)];
+            if (target == null) {
+                throw UnsupportedError($(format!("\"No Rust target for ${{code.targetOS}} ${{code.targetArchitecture}}\"")));
+            }
+            return target;
+        }
+
+        void main(List<String> args) async {
+            await build(args, (input, output) async {
+                if (!input.config.buildCodeAssets) return;
+
+                final code = input.config.code;
+                final target = _rustTarget(code);
+                final manifest = input.packageRoot.resolve(_manifestPath);
+                final targetDir = input.outputDirectoryShared.resolve("cargo/");
+                final cargoArgs = [
+                    "build",
+                    "--release",
+                    "--manifest-path",
+                    manifest.toFilePath(),
+                    "--target",
+                    target,
+                    "--target-dir",
+                    targetDir.toFilePath(),
+                ];
+                final result = await Process.run("cargo", cargoArgs);
+                if (result.exitCode != 0) {
+                    throw ProcessException("cargo", cargoArgs, $(format!("\"${{result.stdout}}${{result.stderr}}\"")), result.exitCode);
+                }
+
+                output.assets.code.add(CodeAsset(
+                    package: input.packageName,
+                    name: _assetName,
+                    linkMode: DynamicLoadingBundled(),
+                    file: targetDir.resolve($(format!("\"$target/release/${{code.targetOS.dylibFileName(_libraryName)}}\""))),
+                ));
+                output.addDependency(manifest);
+                output.addDependency(manifest.resolve("src/"));
+            });
+        }
+    })
+}
+
+/// Add the packages the build hook imports to the `dependencies` of `pubspec`
+fn add_hook_dependencies(pubspec: &str) -> String {
+    let mut lines = pubspec.lines().map(str::to_string).collect::<Vec<_>>();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+
+    let Some(section) = lines.iter().position(|line| line.trim() == "dependencies:") else {
+        lines.push("dependencies:".to_string());
+        lines.extend(
+            HOOK_DEPENDENCIES
+                .iter()
+                .map(|(name, version)| format!("  {name}: {version}")),
+        );
+        return lines.join("\n") + "\n";
+    };
+
+    let section_indent = indent(&lines[section]);
+    let entries = lines[section + 1..]
+        .iter()
+        .take_while(|line| line.trim().is_empty() || indent(line) > section_indent)
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>();
+    let entry_indent = entries
+        .first()
+        .map_or(section_indent + 2, |line| indent(line));
+    let missing = HOOK_DEPENDENCIES
+        .iter()
+        .filter(|(name, _)| {
+            !entries
+                .iter()
+                .any(|line| line.trim_start().starts_with(&format!("{name}:")))
+        })
+        .map(|(name, version)| format!("{:entry_indent$}{name}: {version}", ""))
+        .collect::<Vec<_>>();
+
+    lines.splice(section + 1..section + 1, missing);
+    lines.join("\n") + "\n"
+}
+
+/// Write `hook/build.dart` for the component to its package root, and add the hook's
+/// dependencies to the package's `pubspec.yaml` if there is one
+pub(super) fn write_build_hook(
+    settings: &GenerationSettings,
+    ci: &ComponentInterface,
+    config: &Config,
+    hook: &BuildHookConfig,
+) -> Result<()> {
+    let tokens = generate_build_hook(ci, config, hook)?;
+
+    let package_root = settings.out_dir.join(hook.package_root());
+    let hook_settings = GenerationSettings {
+        out_dir: package_root.join("hook"),
+        try_format_code: settings.try_format_code,
+        cdylib: settings.cdylib.clone(),
+    };
+    std::fs::create_dir_all(&hook_settings.out_dir)?;
+    write_dart_file(&hook_settings, "build", tokens)?;
+
+    let pubspec = package_root.join("pubspec.yaml");
+    if pubspec.exists() {
+        let contents = std::fs::read_to_string(&pubspec)?;
+        std::fs::write(&pubspec, add_hook_dependencies(&contents))?;
+    }
+    Ok(())
+}
@@ -1,37 +1,135 @@
-use std::collections::HashMap;
-use std::collections::HashSet;
-use std::io::Read;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::process::Command;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use camino::Utf8Path;
 
 use genco::fmt;
 use genco::prelude::*;
 use serde::{Deserialize, Serialize};
+use uniffi_bindgen::cargo_metadata::CrateConfigSupplier;
 use uniffi_bindgen::BindgenCrateConfigSupplier;
 use uniffi_bindgen::Component;
 // use uniffi_bindgen::MergeWith;
 use self::render::Renderer;
-use self::types::TypeHelpersRenderer;
-use crate::gen::oracle::DartCodeOracle;
+use self::types::{Runtime, TypeHelpersRenderer};
+use crate::gen::oracle::{AsCodeType, DartCodeOracle};
+use uniffi_bindgen::interface::{FfiFunction, Type};
 use uniffi_bindgen::{BindingGenerator, ComponentInterface};
 
 mod callback_interface;
+mod check;
 mod code_type;
+mod component;
 mod compounds;
 mod custom;
 mod enums;
+pub(crate) mod finalizer;
+mod flutter;
 mod functions;
+mod hook;
 mod objects;
 mod oracle;
+mod package;
 mod primitives;
 mod records;
 mod render;
+mod repr;
 pub mod stream;
 mod types;
 
+pub use check::{check_component, check_library, check_udl, UnsupportedItem};
 pub use code_type::CodeType;
+pub use component::{component_from_udl, components_from_library};
+pub use flutter::FlutterPluginConfig;
+pub use hook::BuildHookConfig;
+pub use repr::{print_repr, ComponentRepr};
+
+/// How Rust invokes callback interface methods implemented in Dart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMode {
+    /// Every method is bound with `Pointer.fromFunction`, so Rust must invoke it
+    /// on the thread running the Dart isolate.
+    #[default]
+    IsolateLocal,
+    /// Every method, and the free hook, is bound with `NativeCallable.listener` so
+    /// Rust may invoke it from any thread. The call is queued on the isolate's event
+    /// loop and returns to Rust right away, so generation fails for methods that
+    /// return a value or declare an error type.
+    Listener,
+}
+
+/// How `_UniffiLib` finds the native library when no loader was set with `setLibraryLoader`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryLoading {
+    /// Open the cdylib, trying `library_search_paths` before the platform default location.
+    #[default]
+    Dynamic,
+    /// Look the symbols up in the running process, for hosts linking the crate statically.
+    Process,
+    /// Load the library bundled with a Flutter app: by name on Android, Linux and Windows, and
+    /// from the process on iOS and macOS, which link the crate statically.
+    Bundled,
+}
+
+/// How the FFI functions of the cdylib are bound.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingMode {
+    /// Each function is looked up in the library opened by `_UniffiLib._open()`.
+    #[default]
+    Lookup,
+    /// Each function is a top-level `@Native` external resolved by the Dart runtime from the
+    /// code asset `asset_id`, as provided by a native-assets build hook.
+    Native,
+}
+
+/// What `write_bindings` writes to the output directory.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// One `.dart` file per component.
+    #[default]
+    Files,
+    /// A Dart package named `package_name`, with the bindings under `lib/src` exported by
+    /// `lib/<package_name>.dart`.
+    Package,
+    /// A Flutter FFI plugin package, building the crate for each platform the app targets and
+    /// loading the library with [`LibraryLoading::Bundled`].
+    FlutterPlugin,
+}
+
+/// Maps a custom type onto a Dart type, configured under `[bindings.dart.custom_types.<Name>]`.
+///
+/// `lift` and `lower` are Dart expressions in which `{}` stands for the value being converted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomTypeConfig {
+    type_name: Option<String>,
+    #[serde(default)]
+    imports: Vec<String>,
+    lift: String,
+    lower: String,
+}
+
+impl CustomTypeConfig {
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    pub fn imports(&self) -> &[String] {
+        &self.imports
+    }
+
+    pub fn lift(&self, builtin: &str) -> String {
+        self.lift.replace("{}", builtin)
+    }
+
+    pub fn lower(&self, custom: &str) -> String {
+        self.lower.replace("{}", custom)
+    }
+}
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -39,6 +137,29 @@ pub struct Config {
     cdylib_name: Option<String>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    #[serde(default)]
+    callback_mode: CallbackMode,
+    #[serde(default)]
+    custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    wrap_custom_types: bool,
+    barrel_file: Option<String>,
+    runtime_package: Option<String>,
+    #[serde(default)]
+    library_loading: LibraryLoading,
+    #[serde(default)]
+    library_search_paths: Vec<String>,
+    library_path_env: Option<String>,
+    #[serde(default)]
+    binding_mode: BindingMode,
+    asset_id: Option<String>,
+    build_hook: Option<BuildHookConfig>,
+    #[serde(default)]
+    output_mode: OutputMode,
+    package_version: Option<String>,
+    flutter_plugin: Option<FlutterPluginConfig>,
+    #[serde(default)]
+    expose_ffi: bool,
 }
 
 impl From<&ComponentInterface> for Config {
@@ -47,6 +168,21 @@ impl From<&ComponentInterface> for Config {
             package_name: Some(ci.namespace().to_owned()),
             cdylib_name: Some(ci.namespace().to_owned()),
             external_packages: HashMap::new(),
+            callback_mode: CallbackMode::default(),
+            custom_types: HashMap::new(),
+            wrap_custom_types: false,
+            barrel_file: None,
+            runtime_package: None,
+            library_loading: LibraryLoading::default(),
+            library_search_paths: Vec::new(),
+            library_path_env: None,
+            binding_mode: BindingMode::default(),
+            asset_id: None,
+            build_hook: None,
+            output_mode: OutputMode::default(),
+            package_version: None,
+            flutter_plugin: None,
+            expose_ffi: false,
         }
     }
 }
@@ -67,6 +203,96 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    pub fn callback_mode(&self) -> CallbackMode {
+        self.callback_mode
+    }
+
+    pub fn custom_type(&self, name: &str) -> Option<&CustomTypeConfig> {
+        self.custom_types.get(name)
+    }
+
+    /// Whether custom types without a `custom_types` entry become wrapper classes instead of typedefs
+    pub fn wrap_custom_types(&self) -> bool {
+        self.wrap_custom_types
+    }
+
+    /// Name of the library exporting the bindings of every generated component, if any
+    pub fn barrel_file(&self) -> Option<&str> {
+        self.barrel_file.as_deref()
+    }
+
+    /// Package providing the runtime, instead of rendering it into the bindings
+    pub fn runtime_package(&self) -> Option<&str> {
+        self.runtime_package.as_deref()
+    }
+
+    pub fn library_loading(&self) -> LibraryLoading {
+        self.library_loading
+    }
+
+    /// Directories searched for the cdylib, relative ones resolved against the working directory
+    pub fn library_search_paths(&self) -> &[String] {
+        &self.library_search_paths
+    }
+
+    /// Environment variable holding a path to load the cdylib from, overriding everything but a loader
+    pub fn library_path_env(&self) -> Option<&str> {
+        self.library_path_env.as_deref()
+    }
+
+    pub fn binding_mode(&self) -> BindingMode {
+        self.binding_mode
+    }
+
+    /// Code asset the `@Native` functions resolve against, the internal library's URI if unset
+    pub fn asset_id(&self) -> Option<&str> {
+        self.asset_id.as_deref()
+    }
+
+    /// Build hook compiling the crate into the code asset, if one should be generated
+    pub fn build_hook(&self) -> Option<&BuildHookConfig> {
+        self.build_hook.as_ref()
+    }
+
+    pub fn output_mode(&self) -> OutputMode {
+        self.output_mode
+    }
+
+    /// Name of the Dart package written in [`OutputMode::Package`], `package_name` if set
+    pub fn dart_package_name(&self, ci: &ComponentInterface) -> String {
+        self.package_name
+            .clone()
+            .unwrap_or_else(|| ci.namespace().to_string())
+    }
+
+    /// Version of the Dart package written in [`OutputMode::Package`]
+    pub fn package_version(&self) -> &str {
+        self.package_version.as_deref().unwrap_or("0.1.0")
+    }
+
+    /// Platform build settings of the plugin written in [`OutputMode::FlutterPlugin`]
+    pub fn flutter_plugin(&self) -> Option<&FlutterPluginConfig> {
+        self.flutter_plugin.as_ref()
+    }
+
+    /// Whether a `<namespace>_ffi.dart` library exports the internal layer, converters and raw
+    /// FFI symbols included, next to the public one
+    pub fn expose_ffi(&self) -> bool {
+        self.expose_ffi
+    }
+
+    /// Import URI for the bindings of another crate.
+    ///
+    /// Crates listed in `external_packages` are imported from the given Dart package, any other
+    /// crate is expected to have its bindings generated next to ours. Either way, the import is
+    /// of the internal layer, which has the converters the public library hides.
+    pub fn external_import(&self, crate_name: &str, namespace: &str) -> String {
+        match self.external_packages.get(crate_name) {
+            Some(package) => format!("package:{package}/src/{namespace}.dart"),
+            None => format!("{namespace}.dart"),
+        }
+    }
 }
 
 pub struct DartWrapper<'a> {
@@ -76,8 +302,8 @@ pub struct DartWrapper<'a> {
 }
 
 impl<'a> DartWrapper<'a> {
-    pub fn new(ci: &'a ComponentInterface, config: &'a Config) -> Self {
-        let type_renderer = TypeHelpersRenderer::new(ci);
+    pub fn new(ci: &'a ComponentInterface, config: &'a Config, runtime: Runtime) -> Self {
+        let type_renderer = TypeHelpersRenderer::new(ci, config, runtime);
         DartWrapper {
             ci,
             config,
@@ -85,93 +311,181 @@ impl<'a> DartWrapper<'a> {
         }
     }
 
-    fn generate(&self) -> dart::Tokens {
-        let package_name = self.config.package_name();
-        let libname = self.config.cdylib_name();
+    /// Types rendered so far whose helpers the runtime provides
+    fn runtime_types(&self) -> impl Iterator<Item = Type> + '_ {
+        self.type_renderer
+            .get_include_names()
+            .into_values()
+            .filter(|ty| self.type_renderer.runtime().provides_helper(ty))
+    }
 
-        let (type_helper_code, functions_definitions) = &self.type_renderer.render();
+    /// Top-level names of the user-facing API, which the public library shows from the internal
+    /// one: functions, types, aliases of external types, the initializers and loader hook, and
+    /// the error the runtime throws
+    fn public_names(&self) -> BTreeSet<String> {
+        let mut names = BTreeSet::from([
+            "initialize".to_string(),
+            "ensureInitialized".to_string(),
+            "UniffiInternalError".to_string(),
+        ]);
+        if self.loads_library() {
+            names.insert("setLibraryLoader".to_string());
+        }
+        names.extend(
+            self.ci
+                .function_definitions()
+                .iter()
+                .map(|fun| DartCodeOracle::fn_name(fun.name())),
+        );
+        for ty in self
+            .helper_types()
+            .chain(self.ci.iter_local_types().cloned())
+        {
+            if self.ci.is_external(&ty) {
+                names.insert(ty.as_codetype().type_label());
+                continue;
+            }
+            match &ty {
+                Type::Record { .. } | Type::Custom { .. } => {}
+                Type::Enum { name, .. } => {
+                    let Some(enm) = self.ci.get_enum_definition(name) else {
+                        continue;
+                    };
+                    // Variants with fields are classes of their own
+                    if !enm.is_flat() {
+                        names.extend(enm.variants().iter().map(|variant| {
+                            format!(
+                                "{}{}",
+                                DartCodeOracle::class_name(variant.name()),
+                                DartCodeOracle::class_name(name)
+                            )
+                        }));
+                    }
+                }
+                // Vtables are registered by the initializers, so their init functions stay internal
+                Type::Object { name, .. } => {
+                    if name.contains("StreamExt") {
+                        names.insert(DartCodeOracle::fn_name(&name.replace("StreamExt", "")));
+                    }
+                }
+                Type::CallbackInterface { .. } => {}
+                _ => continue,
+            }
+            names.insert(ty.as_codetype().type_label());
+        }
+        names
+    }
+
+    /// Names a barrel file shows from the internal library to export this component next to
+    /// `namespaces`: the public ones, but for the initializers and loader hook it defines itself
+    /// and aliases of types the other components export
+    fn barrel_names(&self, namespaces: &HashSet<String>) -> BTreeSet<String> {
+        let mut names = self.public_names();
+        for name in ["initialize", "ensureInitialized", "setLibraryLoader"] {
+            names.remove(name);
+        }
+        for ty in self.helper_types() {
+            if self.is_exported_elsewhere(&ty, namespaces) {
+                names.remove(&ty.as_codetype().type_label());
+            }
+        }
+        names
+    }
 
-        fn uniffi_function_definitions(ci: &ComponentInterface) -> dart::Tokens {
-            let mut definitions = quote!();
-            let mut defined_functions = HashSet::new(); // Track defined function names
+    /// The public library of the component, exporting the user-facing names of the internal
+    /// library in `dir`
+    fn public_exports(&self, dir: &str) -> dart::Tokens {
+        let names = self.public_names();
+        quote! {
+            export $(quoted(format!("{dir}{}.dart", self.ci.namespace()))) show $(for name in names join (, ) => $name);
+        }
+    }
 
-            for fun in ci.iter_ffi_function_definitions() {
-                let fun_name = fun.name().to_owned();
+    /// Whether the bindings open the cdylib themselves, and so provide `setLibraryLoader`
+    fn loads_library(&self) -> bool {
+        self.config.binding_mode() == BindingMode::Lookup
+    }
 
-                // Check for duplicate function names
-                if !defined_functions.insert(fun_name.clone()) {
-                    // Function name already exists, skip to prevent duplicate definition
-                    continue;
-                }
+    /// Types whose helpers are rendered in this component's bindings
+    fn helper_types(&self) -> impl Iterator<Item = Type> + '_ {
+        self.type_renderer
+            .get_include_names()
+            .into_values()
+            .filter(|ty| !self.type_renderer.runtime().provides_helper(ty))
+    }
 
-                let (native_return_type, dart_return_type) = match fun.return_type() {
-                    Some(return_type) => (
-                        quote! { $(DartCodeOracle::ffi_native_type_label(Some(return_type), ci)) },
-                        quote! { $(DartCodeOracle::ffi_dart_type_label(Some(return_type), ci)) },
-                    ),
-                    None => (quote! { Void }, quote! { void }),
-                };
-
-                let (native_args, dart_args) = {
-                    let mut native_args = quote!();
-                    let mut dart_args = quote!();
-
-                    for arg in fun.arguments() {
-                        native_args.append(
-                            quote!($(DartCodeOracle::ffi_native_type_label(Some(&arg.type_()), ci)),),
-                        );
-                        dart_args.append(
-                            quote!($(DartCodeOracle::ffi_dart_type_label(Some(&arg.type_()), ci)),),
-                        );
-                    }
+    fn is_exported_elsewhere(&self, ty: &Type, namespaces: &HashSet<String>) -> bool {
+        self.ci.is_external(ty)
+            && ty.module_path().is_some_and(|module_path| {
+                namespaces.contains(&DartCodeOracle::namespace_for_module_path(
+                    module_path,
+                    self.ci,
+                ))
+            })
+    }
 
-                    if fun.has_rust_call_status_arg() {
-                        native_args.append(quote!(Pointer<RustCallStatus>));
-                        dart_args.append(quote!(Pointer<RustCallStatus>));
-                    }
+    /// The internal library, with the whole of the bindings
+    fn generate(&self) -> dart::Tokens {
+        let libname = self.config.cdylib_name();
 
-                    (native_args, dart_args)
-                };
+        let (type_helper_code, functions_definitions) = &self.type_renderer.render();
 
-                let lookup_fn = quote! {
-                    _dylib.lookupFunction<
-                        $native_return_type Function($(&native_args)),
-                        $(&dart_return_type) Function($(&dart_args))
-                    >($(format!("\"{fun_name}\"")))
-                };
+        // Callback vtables must be registered before Rust can call into Dart, which may
+        // happen before Dart ever passes a callback to Rust
+        let initialization_fns = self
+            .ci
+            .iter_local_types()
+            .filter_map(|ty| ty.as_codetype().initialization_fn())
+            .collect::<Vec<_>>();
 
-                definitions.append(quote! {
-                    late final $dart_return_type Function($dart_args) $fun_name = $lookup_fn;
-                });
+        let runtime = self.type_renderer.runtime();
+        let native = !self.loads_library();
+        // Objects are freed through the finalizer functions the scaffolding adds to the library
+        let has_objects = finalizer::has_objects(self.ci);
+        // A runtime outside the bindings manages RustBuffers through the first component loaded
+        let register_rust_buffer_api = quote! {
+            UniffiRustBufferApi.register(UniffiRustBufferApi(
+                library: $(quoted(self.config.cdylib_name())),
+                alloc: $(self.ci.ffi_rustbuffer_alloc().name()),
+                free: $(self.ci.ffi_rustbuffer_free().name()),
+                reserve: $(self.ci.ffi_rustbuffer_reserve().name()),
+            ));
+        };
+        let check_runtime_version = quote! {
+            if (uniffiRuntimeVersion != $(quoted(types::RUNTIME_VERSION))) {
+                throw UniffiInternalError.panicked($(format!("\"UniFFI runtime version mismatch: bindings version {}, runtime version $uniffiRuntimeVersion\"", types::RUNTIME_VERSION)));
             }
-
-            definitions
-        }
+        };
 
         quote! {
-            library $package_name;
+            library;
 
             $(type_helper_code) // Imports, Types and Type Helper
 
             $(functions_definitions)
 
             class _UniffiLib {
-                _UniffiLib._();
+                $(if *runtime == Runtime::Inline {
+                    _UniffiLib._();
+                } else {
+                    _UniffiLib._() {
+                        $register_rust_buffer_api
+                    }
+                })
 
-                static final DynamicLibrary _dylib = _open();
+                $(if !native {
+                    static final DynamicLibrary _dylib = _open();
 
-                static DynamicLibrary _open() {
-                  if (Platform.isAndroid) return DynamicLibrary.open($(format!("\"${{Directory.current.path}}/lib{libname}.so\"")));
-                  if (Platform.isIOS) return DynamicLibrary.executable();
-                  if (Platform.isLinux) return DynamicLibrary.open($(format!("\"${{Directory.current.path}}/lib{libname}.so\"")));
-                  if (Platform.isMacOS) return DynamicLibrary.open($(format!("\"lib{libname}.dylib\"")));
-                  if (Platform.isWindows) return DynamicLibrary.open($(format!("\"{libname}.dll\"")));
-                  throw UnsupportedError("Unsupported platform: ${Platform.operatingSystem}");
-                }
+                    $(open_library(&libname, self.config))
+                })
 
                 static final _UniffiLib instance = _UniffiLib._();
 
-                $(uniffi_function_definitions(self.ci))
+                $(uniffi_function_definitions(self.ci, self.config, self.ci.iter_ffi_function_definitions()))
+
+                $(if has_objects {
+                    $(finalizer::uniffi_finalizer_definitions(self.ci, self.config))
+                })
 
                 static void _checkApiVersion() {
                     final bindingsVersion = $(self.ci.uniffi_contract_version());
@@ -190,16 +504,363 @@ impl<'a> DartWrapper<'a> {
                 }
             }
 
+            $(if has_objects {
+                $(finalizer::generate_object_finalizer(self.ci, self.config))
+            })
+
+            $(if native {
+                $(native_function_definitions(self.ci, self.config, self.ci.iter_ffi_function_definitions()))
+            } else {
+                DynamicLibrary Function()? _uniffiLibraryLoader;
+
+                void setLibraryLoader(DynamicLibrary Function() loader) {
+                    _uniffiLibraryLoader = loader;
+                }
+            })
+
+            void _uniffiInitializeTypes() {
+                $(for init_fn in &initialization_fns => $init_fn();)
+            }
+
             void initialize() {
-                _UniffiLib._open();
+                $(if *runtime != Runtime::Inline {
+                    // Loading the library registers its RustBuffer functions with the runtime
+                    _UniffiLib.instance;
+                })
+                $(if *runtime == Runtime::Inline && !native {
+                    _UniffiLib._dylib;
+                })
+                _uniffiInitializeTypes();
             }
 
             void ensureInitialized() {
+                $(if let Runtime::Package(_) = runtime {
+                    $check_runtime_version
+                })
                 _UniffiLib._checkApiVersion();
                 _UniffiLib._checkApiChecksums();
+                _uniffiInitializeTypes();
             }
         }
     }
+
+    /// The public library, named after `package_name` when one is set, exporting the user-facing
+    /// API of the internal one in `src/`
+    fn generate_public(&self) -> dart::Tokens {
+        quote! {
+            library$(if let Some(name) = &self.config.package_name => $[' ']$name);
+
+            $(self.public_exports("src/"))
+        }
+    }
+
+    /// `<namespace>_ffi.dart`, exporting the whole internal library for [`Config::expose_ffi`]
+    fn generate_ffi_exports(&self) -> dart::Tokens {
+        quote! {
+            library;
+
+            export $(quoted(format!("src/{}.dart", self.ci.namespace())));
+        }
+    }
+}
+
+/// Native and Dart types of an FFI function's return value and arguments
+struct FfiSignature {
+    native_return_type: dart::Tokens,
+    dart_return_type: dart::Tokens,
+    native_args: Vec<dart::Tokens>,
+    dart_args: Vec<dart::Tokens>,
+}
+
+fn ffi_signature(ci: &ComponentInterface, fun: &FfiFunction) -> FfiSignature {
+    let (native_return_type, dart_return_type) = match fun.return_type() {
+        Some(return_type) => (
+            quote! { $(DartCodeOracle::ffi_native_type_label(Some(return_type), ci)) },
+            quote! { $(DartCodeOracle::ffi_dart_type_label(Some(return_type), ci)) },
+        ),
+        None => (quote! { Void }, quote! { void }),
+    };
+
+    let mut native_args = Vec::new();
+    let mut dart_args = Vec::new();
+    for arg in fun.arguments() {
+        native_args.push(quote!($(DartCodeOracle::ffi_native_type_label(Some(&arg.type_()), ci))));
+        dart_args.push(quote!($(DartCodeOracle::ffi_dart_type_label(Some(&arg.type_()), ci))));
+    }
+    if fun.has_rust_call_status_arg() {
+        native_args.push(quote!(Pointer<RustCallStatus>));
+        dart_args.push(quote!(Pointer<RustCallStatus>));
+    }
+
+    FfiSignature {
+        native_return_type,
+        dart_return_type,
+        native_args,
+        dart_args,
+    }
+}
+
+/// Address of the object free function `fun`, which the finalizer token of each object holds
+fn native_free_address(
+    ci: &ComponentInterface,
+    config: &Config,
+    fun: &FfiFunction,
+) -> dart::Tokens {
+    let fun_name = fun.name();
+    if config.binding_mode() == BindingMode::Native {
+        let FfiSignature {
+            native_return_type,
+            native_args,
+            ..
+        } = ffi_signature(ci, fun);
+        return quote! {
+            Native.addressOf<NativeFunction<$native_return_type Function($(for ty in &native_args join (, ) => $ty))>>($(format!("_{fun_name}"))).cast<Void>()
+        };
+    }
+    quote! {
+        _UniffiLib._dylib.lookup<Void>($(quoted(fun_name)))
+    }
+}
+
+/// FFI functions that haven't been seen yet, as the same symbol may be listed more than once
+fn unique_ffi_functions(functions: impl IntoIterator<Item = FfiFunction>) -> Vec<FfiFunction> {
+    let mut defined_functions = HashSet::new();
+    functions
+        .into_iter()
+        .filter(|fun| defined_functions.insert(fun.name().to_owned()))
+        .collect()
+}
+
+/// Members of `_UniffiLib` exposing each FFI function: looked up in `_dylib`, or forwarding
+/// to the `@Native` externals in [`BindingMode::Native`]
+fn uniffi_function_definitions(
+    ci: &ComponentInterface,
+    config: &Config,
+    functions: impl IntoIterator<Item = FfiFunction>,
+) -> dart::Tokens {
+    let mut definitions = quote!();
+
+    for fun in unique_ffi_functions(functions) {
+        let fun_name = fun.name();
+        let FfiSignature {
+            native_return_type,
+            dart_return_type,
+            native_args,
+            dart_args,
+        } = ffi_signature(ci, &fun);
+
+        if config.binding_mode() == BindingMode::Native {
+            let params = (0..dart_args.len())
+                .map(|i| format!("a{i}"))
+                .collect::<Vec<_>>();
+            definitions.append(quote! {
+                $(&dart_return_type) $fun_name($(for (ty, param) in dart_args.iter().zip(&params) join (, ) => $ty $param)) =>
+                    $(format!("_{fun_name}"))($(for param in &params join (, ) => $param));
+            });
+            continue;
+        }
+
+        let lookup_fn = quote! {
+            _dylib.lookupFunction<
+                $native_return_type Function($(for ty in &native_args join (, ) => $ty)),
+                $(&dart_return_type) Function($(for ty in &dart_args join (, ) => $ty))
+            >($(format!("\"{fun_name}\"")))
+        };
+
+        definitions.append(quote! {
+            late final $(&dart_return_type) Function($(for ty in &dart_args join (, ) => $ty)) $fun_name = $lookup_fn;
+        });
+    }
+
+    definitions
+}
+
+/// Top-level `@Native` externals bound to the FFI functions in [`BindingMode::Native`]
+fn native_function_definitions(
+    ci: &ComponentInterface,
+    config: &Config,
+    functions: impl IntoIterator<Item = FfiFunction>,
+) -> dart::Tokens {
+    let asset_id = config
+        .asset_id()
+        .map(|asset_id| quote!(, assetId: $(quoted(asset_id))));
+
+    let mut definitions = quote!();
+    for fun in unique_ffi_functions(functions) {
+        let fun_name = fun.name();
+        let FfiSignature {
+            native_return_type,
+            dart_return_type,
+            native_args,
+            dart_args,
+        } = ffi_signature(ci, &fun);
+
+        definitions.append(quote! {
+            @Native<$native_return_type Function($(for ty in &native_args join (, ) => $ty))>(symbol: $(quoted(fun_name))$(asset_id.clone()))
+            external $dart_return_type $(format!("_{fun_name}"))($(for ty in &dart_args join (, ) => $ty));
+        });
+        definitions.push();
+    }
+
+    definitions
+}
+
+/// `_UniffiLib._open()`, loading the cdylib `libname` as configured, unless a loader was set
+fn open_library(libname: &str, config: &Config) -> dart::Tokens {
+    let file_name = |extension: &str| quoted(format!("lib{libname}.{extension}"));
+
+    let strategy = match config.library_loading() {
+        LibraryLoading::Process => quote!(return DynamicLibrary.process();),
+        LibraryLoading::Bundled => quote! {
+            if (Platform.isIOS || Platform.isMacOS) return DynamicLibrary.process();
+            if (Platform.isWindows) return DynamicLibrary.open($(quoted(format!("{libname}.dll"))));
+            return DynamicLibrary.open($(file_name("so")));
+        },
+        LibraryLoading::Dynamic => quote! {
+            $(if !config.library_search_paths().is_empty() {
+                final fileName = Platform.isWindows
+                    ? $(quoted(format!("{libname}.dll")))
+                    : Platform.isMacOS || Platform.isIOS ? $(file_name("dylib")) : $(file_name("so"));
+                for (final directory in [$(for path in config.library_search_paths() join (, ) => $(quoted(path)))]) {
+                  final file = File($(format!("\"$directory/$fileName\""))).absolute;
+                  if (file.existsSync()) return DynamicLibrary.open(file.path);
+                }
+            })
+            if (Platform.isAndroid) return DynamicLibrary.open($(format!("\"${{Directory.current.path}}/lib{libname}.so\"")));
+            if (Platform.isIOS) return DynamicLibrary.executable();
+            if (Platform.isLinux) return DynamicLibrary.open($(format!("\"${{Directory.current.path}}/lib{libname}.so\"")));
+            if (Platform.isMacOS) return DynamicLibrary.open($(file_name("dylib")));
+            if (Platform.isWindows) return DynamicLibrary.open($(format!("\"{libname}.dll\"")));
+            throw UnsupportedError("Unsupported platform: ${Platform.operatingSystem}");
+        },
+    };
+
+    quote! {
+        static DynamicLibrary _open() {
+          final loader = _uniffiLibraryLoader;
+          if (loader != null) return loader();
+          $(if let Some(variable) = config.library_path_env() {
+              final path = Platform.environment[$(quoted(variable))];
+              if (path != null && path.isNotEmpty) return DynamicLibrary.open(path);
+          })
+          $strategy
+        }
+    }
+}
+
+/// The runtime library imported by bindings using a `runtime` other than [`Runtime::Inline`]
+fn generate_runtime_library(
+    runtime: Runtime,
+    runtime_types: impl IntoIterator<Item = Type>,
+) -> dart::Tokens {
+    let ci = ComponentInterface::new(types::SHARED_RUNTIME_LIBRARY);
+    let config = Config::default();
+    let type_renderer = TypeHelpersRenderer::new(&ci, &config, runtime);
+
+    quote! {
+        library $(types::SHARED_RUNTIME_LIBRARY);
+
+        $(type_renderer.render_runtime(runtime_types))
+    }
+}
+
+/// Write the versioned runtime package `package_name` to `out_dir`, for bindings configured
+/// with `runtime_package` to import
+pub fn generate_runtime_package(
+    out_dir: &Utf8Path,
+    package_name: &str,
+    try_format_code: bool,
+) -> Result<()> {
+    let lib_dir = out_dir.join("lib");
+    std::fs::create_dir_all(&lib_dir)?;
+    std::fs::write(
+        out_dir.join("pubspec.yaml"),
+        format!(
+            "name: {package_name}
+description: Runtime shared by Dart bindings generated with uniffi-dart
+version: {version}
+
+environment:
+  sdk: '>=3.5.0'
+dependencies:
+  ffi: ^2.0.1
+",
+            version = types::RUNTIME_VERSION,
+        ),
+    )?;
+
+    let settings = uniffi_bindgen::GenerationSettings {
+        out_dir: lib_dir,
+        try_format_code,
+        cdylib: None,
+    };
+    let tokens = generate_runtime_library(Runtime::Package(package_name.to_string()), []);
+    write_dart_file(&settings, types::SHARED_RUNTIME_LIBRARY, tokens)
+}
+
+/// Library exporting the bindings of every component from the internal libraries in `dir`, with
+/// initializers covering them all
+fn generate_barrel_library(name: &str, dir: &str, wrappers: &[DartWrapper]) -> dart::Tokens {
+    let namespaces = wrappers
+        .iter()
+        .map(|wrapper| wrapper.ci.namespace().to_string())
+        .collect::<HashSet<_>>();
+    let exports = wrappers
+        .iter()
+        .map(|wrapper| (wrapper.ci.namespace(), wrapper.barrel_names(&namespaces)))
+        .collect::<Vec<_>>();
+    let loaders = wrappers
+        .iter()
+        .filter(|wrapper| wrapper.loads_library())
+        .collect::<Vec<_>>();
+
+    quote! {
+        library $name;
+
+        $(if !loaders.is_empty() {
+            import "dart:ffi";
+        })
+        $(for wrapper in wrappers join ($['\r']) =>
+            import $(quoted(format!("{dir}{}.dart", wrapper.ci.namespace()))) as $(wrapper.ci.namespace());
+        )
+
+        $(for (namespace, names) in exports join ($['\r']) =>
+            export $(quoted(format!("{dir}{namespace}.dart"))) show $(for name in names join (, ) => $name);
+        )
+
+        void initialize() {
+            $(for wrapper in wrappers join ($['\r']) => $(wrapper.ci.namespace()).initialize();)
+        }
+
+        void ensureInitialized() {
+            $(for wrapper in wrappers join ($['\r']) => $(wrapper.ci.namespace()).ensureInitialized();)
+        }
+
+        $(if !loaders.is_empty() {
+            void setLibraryLoader(DynamicLibrary Function() loader) {
+                $(for wrapper in loaders join ($['\r']) => $(wrapper.ci.namespace()).setLibraryLoader(loader);)
+            }
+        })
+    }
+}
+
+/// Write `tokens` to `{name}.dart` in the output directory
+fn write_dart_file(
+    settings: &uniffi_bindgen::GenerationSettings,
+    name: &str,
+    tokens: dart::Tokens,
+) -> Result<()> {
+    let file = std::fs::File::create(settings.out_dir.join(format!("{name}.dart")))?;
+    let mut w = fmt::IoWriter::new(file);
+
+    let mut fmt = fmt::Config::from_lang::<Dart>();
+    if settings.try_format_code {
+        fmt = fmt.with_indentation(fmt::Indentation::Space(2));
+    }
+    let config = dart::Config::default();
+
+    tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+    Ok(())
 }
 
 pub struct DartBindingGenerator;
@@ -212,20 +873,104 @@ impl BindingGenerator for DartBindingGenerator {
         settings: &uniffi_bindgen::GenerationSettings,
         components: &[uniffi_bindgen::Component<Self::Config>],
     ) -> Result<()> {
-        for Component { ci, config, .. } in components {
-            let filename = settings.out_dir.join(format!("{}.dart", ci.namespace()));
-            let tokens = DartWrapper::new(ci, config).generate();
-            let file = std::fs::File::create(filename)?;
+        for Component { ci, config } in components {
+            if config.callback_mode() != CallbackMode::Listener {
+                continue;
+            }
+            let unsupported = check::check_listener_callbacks(ci);
+            if !unsupported.is_empty() {
+                bail!(
+                    "`callback_mode = \"listener\"` only binds callback methods returning nothing and throwing nothing:\n{}",
+                    unsupported
+                        .iter()
+                        .map(|item| format!("  {item}"))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+            }
+        }
+
+        // Components of one cdylib share a runtime, so their bindings can be imported together
+        let runtime = match components
+            .iter()
+            .find_map(|component| component.config.runtime_package())
+        {
+            Some(package) => Runtime::Package(package.to_string()),
+            None if components.len() > 1 => Runtime::Shared,
+            None => Runtime::Inline,
+        };
+        let wrappers = components
+            .iter()
+            .map(|Component { ci, config }| DartWrapper::new(ci, config, runtime.clone()))
+            .collect::<Vec<_>>();
+
+        // The public libraries go to the output directory, or to `lib` for a package, with the
+        // internal ones they export from in `src` below it
+        let package = components
+            .iter()
+            .find(|component| component.config.output_mode() != OutputMode::Files);
+        let public_settings = uniffi_bindgen::GenerationSettings {
+            out_dir: match package {
+                Some(_) => settings.out_dir.join("lib"),
+                None => settings.out_dir.clone(),
+            },
+            try_format_code: settings.try_format_code,
+            cdylib: settings.cdylib.clone(),
+        };
+        let internal_settings = uniffi_bindgen::GenerationSettings {
+            out_dir: public_settings.out_dir.join("src"),
+            try_format_code: settings.try_format_code,
+            cdylib: settings.cdylib.clone(),
+        };
+        std::fs::create_dir_all(&internal_settings.out_dir)?;
+
+        let mut runtime_types = BTreeMap::new();
+        for wrapper in &wrappers {
+            let namespace = wrapper.ci.namespace();
+            write_dart_file(&internal_settings, namespace, wrapper.generate())?;
+            // A package exports the public API from the library named after it instead
+            if package.is_none() {
+                write_dart_file(&public_settings, namespace, wrapper.generate_public())?;
+            }
+            if wrapper.config.expose_ffi() {
+                let tokens = wrapper.generate_ffi_exports();
+                write_dart_file(&public_settings, &format!("{namespace}_ffi"), tokens)?;
+            }
+            runtime_types.extend(
+                wrapper
+                    .runtime_types()
+                    .map(|ty| (ty.as_codetype().canonical_name(), ty)),
+            );
+        }
+
+        if runtime == Runtime::Shared {
+            let tokens = generate_runtime_library(runtime.clone(), runtime_types.into_values());
+            write_dart_file(&internal_settings, types::SHARED_RUNTIME_LIBRARY, tokens)?;
+        }
 
-            let mut w = fmt::IoWriter::new(file);
+        if let Some(name) = components
+            .iter()
+            .find_map(|component| component.config.barrel_file())
+        {
+            let tokens = generate_barrel_library(name, "src/", &wrappers);
+            write_dart_file(&public_settings, name, tokens)?;
+        }
+
+        if let Some(Component { ci, config }) = package {
+            package::write_package(settings, ci, config, &runtime, &wrappers)?;
+            if config.output_mode() == OutputMode::FlutterPlugin {
+                flutter::write_plugin_platforms(settings, ci, config)?;
+            }
+        }
 
-            let mut fmt = fmt::Config::from_lang::<Dart>();
-            if settings.try_format_code {
-                fmt = fmt.with_indentation(fmt::Indentation::Space(2));
+        for Component { ci, config } in components {
+            if let Some(hook) = config.build_hook() {
+                hook::write_build_hook(settings, ci, config, hook)?;
             }
-            let config = dart::Config::default();
+        }
 
-            tokens.format_file(&mut w.as_formatter(&fmt), &config)?;
+        if !settings.try_format_code {
+            return Ok(());
         }
 
         // Run full Dart formatter on the output directory as a best-effort step.
@@ -267,20 +1012,33 @@ impl BindingGenerator for DartBindingGenerator {
                     .clone()
                     .unwrap_or_else(|| format!("uniffi_{}", c.ci.namespace()))
             });
+            // A plugin's library isn't next to the app, wherever it's started from
+            if c.config.output_mode == OutputMode::FlutterPlugin
+                && c.config.library_loading == LibraryLoading::Dynamic
+            {
+                c.config.library_loading = LibraryLoading::Bundled;
+            }
         }
         Ok(())
     }
 }
 
-pub struct LocalConfigSupplier(String);
-impl BindgenCrateConfigSupplier for LocalConfigSupplier {
-    fn get_udl(&self, _crate_name: &str, _udl_name: &str) -> Result<String> {
-        let file = std::fs::File::open(self.0.clone())?;
-        let mut reader = std::io::BufReader::new(file);
-        let mut content = String::new();
-        reader.read_to_string(&mut content)?;
-        Ok(content)
+/// A config supplier locating each crate's UDL and `uniffi.toml` through `cargo metadata`,
+/// run offline for the workspace of `manifest_path`, or of the current directory
+pub fn cargo_metadata_config_supplier(
+    manifest_path: Option<&Utf8Path>,
+    no_deps: bool,
+) -> Result<CrateConfigSupplier> {
+    let mut command = cargo_metadata::MetadataCommand::new();
+    command.other_options(vec!["--offline".to_string()]);
+    if let Some(manifest_path) = manifest_path {
+        command.manifest_path(manifest_path);
+    }
+    if no_deps {
+        command.no_deps();
     }
+    let metadata = command.exec().context("running `cargo metadata`")?;
+    Ok(CrateConfigSupplier::from(metadata))
 }
 
 pub fn generate_dart_bindings(
@@ -291,19 +1049,26 @@ pub fn generate_dart_bindings(
     library_mode: bool,
 ) -> anyhow::Result<()> {
     if library_mode {
-        uniffi_bindgen::library_mode::generate_bindings(
+        let crate_root = udl_file
+            .parent()
+            .and_then(|src| src.parent())
+            .context("UDL file is expected in the crate's `src` directory")?;
+        let out_dir = match out_dir_override {
+            Some(out_dir) => out_dir,
+            None => udl_file
+                .parent()
+                .context("UDL file has no parent directory")?,
+        };
+        generate_dart_bindings_from_library(
             library_file,
             None,
-            &DartBindingGenerator {},
-            &LocalConfigSupplier(udl_file.to_string()),
-            None,
-            out_dir_override.unwrap(),
+            &cargo_metadata_config_supplier(Some(&crate_root.join("Cargo.toml")), false)?,
+            config_file_override,
+            out_dir,
             true,
-        )?;
-        Ok(())
+        )
     } else {
-        uniffi_bindgen::generate_external_bindings(
-            &DartBindingGenerator {},
+        generate_dart_bindings_from_udl(
             udl_file,
             config_file_override,
             out_dir_override,
@@ -313,3 +1078,53 @@ pub fn generate_dart_bindings(
         )
     }
 }
+
+/// Generate bindings for the component defined by `udl_file`
+pub fn generate_dart_bindings_from_udl(
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    out_dir_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+    crate_name: Option<&str>,
+    try_format_code: bool,
+) -> anyhow::Result<()> {
+    if let Some(library_file) = library_file {
+        let ci = component_from_udl(udl_file, Some(library_file), crate_name)?;
+        finalizer::check_library(library_file, [&ci])?;
+    }
+    uniffi_bindgen::generate_external_bindings(
+        &DartBindingGenerator {},
+        udl_file,
+        config_file_override,
+        out_dir_override,
+        library_file,
+        crate_name,
+        try_format_code,
+    )
+}
+
+/// Generate bindings for every component found in the metadata of `library_file`,
+/// or only for `crate_name` when given
+pub fn generate_dart_bindings_from_library(
+    library_file: &Utf8Path,
+    crate_name: Option<String>,
+    config_supplier: &dyn BindgenCrateConfigSupplier,
+    config_file_override: Option<&Utf8Path>,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> anyhow::Result<()> {
+    finalizer::check_library(
+        library_file,
+        &components_from_library(library_file, crate_name.as_deref(), config_supplier)?,
+    )?;
+    uniffi_bindgen::library_mode::generate_bindings(
+        library_file,
+        crate_name,
+        &DartBindingGenerator {},
+        config_supplier,
+        config_file_override,
+        out_dir,
+        try_format_code,
+    )?;
+    Ok(())
+}
@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use crate::gen::callback_interface::{
     generate_callback_functions, generate_callback_interface,
     generate_callback_interface_vtable_init_function, generate_callback_vtable_interface,
+    vtable_init_fn_name,
 };
 use crate::gen::CodeType;
 use heck::ToLowerCamelCase;
@@ -15,6 +16,7 @@ use crate::gen::oracle::{AsCodeType, DartCodeOracle};
 use crate::gen::render::AsRenderable;
 use crate::gen::render::{Renderable, TypeHelperRenderer};
 
+use super::native_free_address;
 use super::stream::generate_stream;
 
 #[derive(Debug)]
@@ -49,6 +51,12 @@ impl CodeType for ObjectCodeType {
             ObjectImpl::Trait => todo!("trait objects not supported"),
         }
     }
+
+    fn initialization_fn(&self) -> Option<String> {
+        self.imp
+            .has_callback_interface()
+            .then(|| vtable_init_fn_name(&self.id))
+    }
 }
 
 impl Renderable for ObjectCodeType {
@@ -62,12 +70,23 @@ impl Renderable for ObjectCodeType {
         }
     }
 }
+/// The primary constructor `new` is the unnamed `ClassName(...)`, any other is `ClassName.name(...)`
+pub(super) fn dart_constructor_name(obj_name: &str, constructor_name: &str) -> String {
+    let cls_name = DartCodeOracle::class_name(obj_name);
+    if constructor_name == "new" {
+        cls_name
+    } else {
+        format!("{cls_name}.{}", DartCodeOracle::fn_name(constructor_name))
+    }
+}
+
 pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> dart::Tokens {
     type_helper.include_once_check(obj.name(), &obj.as_type());
 
     if obj.has_callback_interface() {
         let interface = generate_callback_interface(
             obj.name(),
+            obj.docstring(),
             &obj.as_codetype().ffi_converter_name(),
             &obj.methods(),
             type_helper,
@@ -91,7 +110,7 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
     }
 
     let cls_name = &DartCodeOracle::class_name(obj.name());
-    let finalizer_cls_name = &format!("{cls_name}Finalizer");
+    let free_address_name = &format!("_{cls_name}Free");
     let lib_instance = &DartCodeOracle::find_lib_instance();
     let ffi_object_free_name = obj.ffi_object_free().name();
     let ffi_object_clone_name = obj.ffi_object_clone().name();
@@ -108,11 +127,7 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
         let ffi_func_name = constructor.ffi_func().name();
         let constructor_name = constructor.name();
 
-        let dart_constructor_decl = if constructor_name == "new" {
-            quote!($cls_name)
-        } else {
-            quote!($cls_name.$(DartCodeOracle::fn_name(constructor_name)))
-        };
+        let dart_constructor_decl = dart_constructor_name(obj.name(), constructor_name);
 
         // Check if function can throw errors
         let error_handler = if let Some(error_type) = constructor.throws_type() {
@@ -139,13 +154,14 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
 
         quote! {
             // Public constructor
+            $(DartCodeOracle::doc_comment(constructor.docstring()))
             $dart_constructor_decl($dart_params) : _ptr = rustCall((status) =>
                 $lib_instance.$ffi_func_name(
                     $ffi_call_args status
                 ),
                 $error_handler
             ) {
-                 _$finalizer_cls_name.attach(this, _ptr, detach: this);
+                _attachFinalizer();
             }
         }
     });
@@ -161,7 +177,7 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
             class $(&error_handler_name) extends UniffiRustCallStatusErrorHandler {
                 @override
                 Exception lift(RustBuffer errorBuf) {
-                    return $(cls_name).read(errorBuf.asUint8List()).value;
+                    return errorBuf.consume((bytes) => $(cls_name).read(bytes).value);
                 }
             }
 
@@ -171,10 +187,11 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
         quote!()
     };
 
-    let implements_exception = if is_error_interface {
-        quote!( implements Exception)
+    // Finalizable keeps the object alive while a call is using its pointer
+    let implements = if is_error_interface {
+        quote!(implements Exception, Finalizable)
     } else {
-        quote!()
+        quote!(implements Finalizable)
     };
 
     // Generate toString() method for error interfaces
@@ -192,16 +209,22 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
     };
 
     quote! {
-        final _$finalizer_cls_name = Finalizer<Pointer<Void>>((ptr) {
-          rustCall((status) => $lib_instance.$ffi_object_free_name(ptr, status));
-        });
+        final $free_address_name = $(native_free_address(type_helper.get_ci(), type_helper.get_config(), obj.ffi_object_free()));
 
-        class $cls_name $implements_exception {
+        $(DartCodeOracle::doc_comment(obj.docstring()))
+        class $cls_name $implements {
             late final Pointer<Void> _ptr;
+            late final Pointer<Void> _finalizerToken;
+            bool _disposed = false;
 
             // Private constructor for internal use / lift
             $cls_name._(this._ptr) {
-                _$finalizer_cls_name.attach(this, _ptr, detach: this);
+                _attachFinalizer();
+            }
+
+            void _attachFinalizer() {
+                _finalizerToken = $lib_instance.finalizerToken(_ptr, $free_address_name);
+                _uniffiObjectFinalizer.attach(this, _finalizerToken, detach: this);
             }
 
             // Public constructors generated from UDL
@@ -217,6 +240,9 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
             }
 
             Pointer<Void> uniffiClonePointer() {
+                if (_disposed) {
+                    throw StateError($(quoted(format!("{cls_name} used after dispose()"))));
+                }
                 return rustCall((status) => $lib_instance.$ffi_object_clone_name(_ptr, status));
             }
 
@@ -237,11 +263,33 @@ pub fn generate_object(obj: &Object, type_helper: &dyn TypeHelperRenderer) -> da
                 return 8;
             }
 
+            $(DartCodeOracle::doc_comment(Some("Free the Rust object now rather than when this is garbage collected.\nFurther calls throw a [StateError], and disposing again does nothing.")))
             void dispose() {
-                _$finalizer_cls_name.detach(this);
+                if (_disposed) {
+                    return;
+                }
+                _disposed = true;
+                _uniffiObjectFinalizer.detach(this);
+                $lib_instance.finalizerTokenFree(_finalizerToken);
                 rustCall((status) => $lib_instance.$ffi_object_free_name(_ptr, status));
             }
 
+            $(DartCodeOracle::doc_comment(Some("Run [action] with this object and dispose of it afterwards, once the\nreturned future completes if [action] is asynchronous.")))
+            R use<R>(R Function($cls_name) action) {
+                final R result;
+                try {
+                    result = action(this);
+                } catch (_) {
+                    dispose();
+                    rethrow;
+                }
+                if (result is Future) {
+                    return result.whenComplete(dispose) as R;
+                }
+                dispose();
+                return result;
+            }
+
             $to_string_method
 
             $(for mt in &obj.methods() => $(generate_method(mt, type_helper)))
@@ -277,7 +325,9 @@ pub fn generate_method(func: &Method, type_helper: &dyn TypeHelperRenderer) -> d
         quote!(null)
     };
 
-    if func.is_async() {
+    let doc = DartCodeOracle::doc_comment(func.docstring());
+
+    let body = if func.is_async() {
         quote!(
             Future<$ret> $(DartCodeOracle::fn_name(func.name()))($args) {
                 return uniffiRustCallAsync(
@@ -314,5 +364,10 @@ pub fn generate_method(func: &Method, type_helper: &dyn TypeHelperRenderer) -> d
                 )), $error_handler);
             }
         )
+    };
+
+    quote! {
+        $doc
+        $body
     }
 }
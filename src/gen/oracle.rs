@@ -86,6 +86,15 @@ impl DartCodeOracle {
     //     }
     // }
 
+    /// Renders a Rust docstring as a `///` comment on the following declaration
+    pub fn doc_comment(docstring: Option<&str>) -> dart::Tokens {
+        let mut tokens = quote!($(dart::doc_comment(docstring.into_iter().flat_map(str::lines).map(str::trim_end))));
+        if docstring.is_some() {
+            tokens.push();
+        }
+        tokens
+    }
+
     pub fn find_lib_instance() -> dart::Tokens {
         quote!(_UniffiLib.instance)
     }
@@ -103,15 +112,27 @@ impl DartCodeOracle {
 
     /// Helper method to fully qualify imports of external `RustBuffer`s
     fn rust_buffer_name_with_path(module_path: &str, ci: &ComponentInterface) -> dart::Tokens {
-        let namespace = ci
-            .namespace_for_module_path(module_path)
-            .expect("module path should exist");
+        let namespace = Self::namespace_for_module_path(module_path, ci);
         if namespace != ci.namespace() {
             return quote!($(namespace).RustBuffer);
         }
         quote!(RustBuffer)
     }
 
+    /// Namespace of the crate owning `module_path`, which is also the prefix its bindings are imported under.
+    ///
+    /// Outside of library mode the namespaces of other crates are unknown, so the crate name is used instead.
+    pub fn namespace_for_module_path(module_path: &str, ci: &ComponentInterface) -> String {
+        let crate_name = Self::crate_name_for_module_path(module_path);
+        ci.namespace_for_module_path(crate_name)
+            .map(str::to_string)
+            .unwrap_or_else(|_| crate_name.to_string())
+    }
+
+    pub fn crate_name_for_module_path(module_path: &str) -> &str {
+        module_path.split("::").next().unwrap_or(module_path)
+    }
+
     // TODO: Replace instances of `generate_ffi_dart_type` with ffi_type_label
     pub fn ffi_dart_type_label(
         ffi_type: Option<&FfiType>,
@@ -313,128 +334,15 @@ impl DartCodeOracle {
         }
     }
 
-    /// Get the native Dart FFI type rendering based on `Type`.
-    pub fn native_type_label(
-        native_ret_type: Option<&Type>,
-        ci: &ComponentInterface,
-    ) -> dart::Tokens {
-        if let Some(ret_type) = native_ret_type {
-            match ret_type {
-                Type::UInt8 => quote!(Uint8),
-                Type::UInt16 => quote!(Uint16),
-                Type::UInt32 => quote!(Uint32),
-                Type::UInt64 => quote!(Uint64),
-                Type::Int8 => quote!(Int8),
-                Type::Int16 => quote!(Int16),
-                Type::Int32 => quote!(Int32),
-                Type::Int64 => quote!(Int64),
-                Type::Float32 => quote!(Float),
-                Type::Float64 => quote!(Double),
-                Type::Boolean => quote!(Int8),
-                Type::Bytes => quote!(RustBuffer),
-                Type::String => quote!(RustBuffer),
-                Type::Timestamp => quote!(Int64),
-                Type::Duration => quote!(Int64),
-                Type::Optional { inner_type } => match **inner_type {
-                    Type::String => quote!(RustBuffer),
-                    _ => quote!(RustBuffer),
-                },
-                Type::Sequence { .. } => quote!(RustBuffer),
-                Type::Map { .. } => quote!(RustBuffer),
-                Type::Object { .. } => quote!(Pointer<Void>),
-                Type::Enum { .. } => quote!(Int32),
-                Type::Record { module_path, .. } => {
-                    Self::rust_buffer_name_with_path(module_path, ci)
-                }
-                Type::Custom { name, .. } => {
-                    let class_name = &DartCodeOracle::class_name(name);
-                    quote!($class_name)
-                }
-                _ => quote!(Pointer<Void>),
-            }
-        } else {
-            quote!(Void)
-        }
-    }
-
-    /// Get the native Dart FFI type rendering based on `Type`.
-    pub fn native_dart_type_label(
-        native_ret_type: Option<&Type>,
-        ci: &ComponentInterface,
-    ) -> dart::Tokens {
-        if let Some(ret_type) = native_ret_type {
-            match ret_type {
-                Type::UInt8
-                | Type::UInt16
-                | Type::UInt32
-                | Type::UInt64
-                | Type::Int8
-                | Type::Int16
-                | Type::Int32
-                | Type::Int64 => quote!(int), // Adjust based on actual FFI size
-                Type::Float32 => quote!(double),
-                Type::Float64 => quote!(double),
-                Type::Boolean => quote!(int),
-                Type::Bytes => quote!(RustBuffer),
-                Type::String => quote!(RustBuffer),
-                Type::Timestamp => quote!(int),
-                Type::Duration => quote!(int),
-                Type::Optional { inner_type } => match **inner_type {
-                    Type::String => quote!(RustBuffer),
-                    _ => quote!(RustBuffer),
-                },
-                Type::Sequence { .. } => quote!(RustBuffer),
-                Type::Map { .. } => quote!(RustBuffer),
-                Type::Object { .. } => quote!(Pointer<Void>),
-                Type::Enum { .. } => quote!(int),
-                Type::Record { module_path, .. } => {
-                    Self::rust_buffer_name_with_path(module_path, ci)
-                }
-                Type::Custom { name, .. } => {
-                    let type_name = &DartCodeOracle::class_name(name);
-                    quote!($type_name)
-                }
-                _ => quote!(dynamic),
-            }
-        } else {
-            quote!(void)
-        }
-    }
-
     // Method to get the appropriate callback parameter type
     pub fn callback_param_type(
         arg_type: &Type,
         arg_name: &str,
         ci: &ComponentInterface,
     ) -> dart::Tokens {
-        match arg_type {
-            Type::Boolean => quote!(int $arg_name),
-            Type::Bytes => quote!(RustBuffer $(arg_name)Buffer),
-            Type::String => quote!(RustBuffer $(arg_name)Buffer),
-            Type::Optional { inner_type } => {
-                if let Type::String = **inner_type {
-                    quote!(RustBuffer $(arg_name)Buffer)
-                } else {
-                    //let type_label = DartCodeOracle::dart_type_label(Some(arg_type));
-                    quote!(RustBuffer $arg_name)
-                }
-            }
-            Type::Sequence { inner_type } => {
-                if let Type::Int32 = **inner_type {
-                    quote!(RustBuffer $(arg_name)Buffer)
-                } else {
-                    //let type_label = DartCodeOracle::dart_type_label(Some(arg_type));
-                    quote!(RustBuffer $arg_name)
-                }
-            }
-            Type::Record { module_path, .. } => {
-                quote!($(Self::rust_buffer_name_with_path(module_path, ci)) $arg_name)
-            }
-            _ => {
-                let type_label = DartCodeOracle::dart_type_label(Some(arg_type));
-                quote!($type_label $arg_name)
-            }
-        }
+        // Arguments arrive exactly as the scaffolding lowered them
+        let ffi_type = FfiType::from(arg_type);
+        quote!($(Self::ffi_dart_type_label(Some(&ffi_type), ci)) $arg_name)
     }
 
     // Method to generate code for handling callback return values
@@ -443,84 +351,37 @@ impl DartCodeOracle {
         method_name: &str,
         args: Vec<dart::Tokens>,
     ) -> dart::Tokens {
-        match ret_type {
-            Type::Boolean => {
-                // For boolean return values
-                quote!(
-                    final result = obj.$method_name($(for arg in &args => $arg,));
-                    outReturn.value = result ? 1 : 0;
-                )
-            }
-            Type::Optional { inner_type } => {
-                // For optional return values
-                if let Type::String = **inner_type {
-                    quote!(
-                        final result = obj.$method_name($(for arg in &args => $arg,));
-                        if (result == null) {
-                            outReturn.ref = toRustBuffer(Uint8List.fromList([0]));
-                        } else {
-                            final lowered = FfiConverterOptionalString.lower(result);
-                            outReturn.ref = toRustBuffer(lowered.asUint8List());
-                        }
-                    )
-                } else {
-                    let lowered = ret_type.as_codetype().ffi_converter_name();
-                    quote!(
-                        final result = obj.$method_name($(for arg in &args => $arg,));
-                        if (result == null) {
-                            outReturn.ref = toRustBuffer(Uint8List.fromList([0]));
-                        } else {
-                            final lowered = $lowered.lower(result);
-                            final buffer = Uint8List(1 + lowered.length);
-                            buffer[0] = 1;
-                            buffer.setAll(1, lowered.asUint8List());
-                            outReturn.ref = toRustBuffer(buffer);
-                        }
-                    )
-                }
-            }
-            Type::String => {
-                // For string return values
-                quote!(
-                    final result = obj.$method_name($(for arg in &args => $arg,));
-                    outReturn.ref = FfiConverterString.lower(result);
-                    status.code = CALL_SUCCESS;
-                )
-            }
-            Type::Sequence { inner_type } => {
-                if let Type::Int32 = **inner_type {
-                    // For int32 sequence return values
-                    quote!(
-                        final result = obj.$method_name($(for arg in &args => $arg,));
-                        outReturn.ref = FfiConverterSequenceInt32.lower(result);
-                    )
-                } else {
-                    // For other sequence types
-                    let lowered = ret_type.as_codetype().ffi_converter_name();
-                    quote!(
-                        final result = obj.$method_name($(for arg in &args => $arg,));
-                        outReturn.ref = $lowered.lower(result);
-                    )
-                }
-            }
-            _ => {
-                // For other return types
-                let lowered = ret_type.as_codetype().ffi_converter_name();
-                quote!(
-                    final result = obj.$method_name($(for arg in &args => $arg,));
-                    outReturn.ref = $lowered.lower(result);
-                )
-            }
-        }
+        let lowered = match ret_type {
+            // Callback trait objects are handed back to Rust as a handle disguised as a pointer
+            Type::Object {
+                imp: ObjectImpl::CallbackTrait,
+                ..
+            } => quote!(Pointer<Void>.fromAddress($(ret_type.as_codetype().lower())(result))),
+            _ => quote!($(ret_type.as_codetype().lower())(result)),
+        };
+
+        // Buffers are written as structs, everything else is a plain native value
+        let assign = match FfiType::from(ret_type) {
+            FfiType::RustBuffer(_) => quote!(outReturn.ref = $lowered;),
+            _ => quote!(outReturn.value = $lowered;),
+        };
+
+        quote!(
+            final result = obj.$method_name($(for arg in &args => $arg,));
+            $assign
+            status.code = CALL_SUCCESS;
+        )
     }
 
     // Method to get the appropriate return type for callback functions
-    pub fn callback_out_return_type(ret_type: Option<&Type>) -> dart::Tokens {
+    pub fn callback_out_return_type(
+        ret_type: Option<&Type>,
+        ci: &ComponentInterface,
+    ) -> dart::Tokens {
         if let Some(ret) = ret_type {
-            match ret {
-                Type::Boolean => quote!(Pointer<Int8>),
-                _ => quote!(Pointer<RustBuffer>),
-            }
+            // The out-pointer must match the scaffolding's vtable signature exactly
+            let native_type = Self::ffi_native_type_label(Some(&FfiType::from(ret)), ci);
+            quote!(Pointer<$native_type>)
         } else {
             quote!(Pointer<Void>)
         }
@@ -541,39 +402,20 @@ impl DartCodeOracle {
         arg_idx: usize,
     ) -> dart::Tokens {
         // Use index-based variable names to avoid conflicts
-        if let Type::Boolean = arg_type {
-            quote!(final bool_arg$(arg_idx) = $arg_name == 1;)
-        } else if let Type::Bytes = arg_type {
-            quote!(final arg$(arg_idx) = FfiConverterUint8List.lift($(arg_name)Buffer);)
-        } else if let Type::String = arg_type {
-            quote!(final arg$(arg_idx) = FfiConverterString.lift($(arg_name)Buffer);)
-        } else if let Type::Optional { inner_type } = arg_type {
-            if let Type::String = **inner_type {
-                quote!(final arg$(arg_idx) = FfiConverterOptionalString.lift($(arg_name)Buffer);)
-            } else {
-                let converter = arg_type.as_codetype().ffi_converter_name();
-                quote!(final arg$(arg_idx) = $converter.lift($arg_name);)
-            }
-        } else if let Type::Sequence { inner_type } = arg_type {
-            if let Type::Int32 = **inner_type {
-                quote!(final arg$(arg_idx) = FfiConverterSequenceInt32.lift($(arg_name)Buffer);)
-            } else {
-                let converter = arg_type.as_codetype().ffi_converter_name();
-                quote!(final arg$(arg_idx) = $converter.lift($arg_name);)
-            }
-        } else {
-            let converter = arg_type.as_codetype().ffi_converter_name();
-            quote!(final arg$(arg_idx) = $converter.lift($arg_name);)
-        }
+        let lifted = match arg_type {
+            // Callback trait objects come in as a handle disguised as a pointer
+            Type::Object {
+                imp: ObjectImpl::CallbackTrait,
+                ..
+            } => quote!($(arg_type.as_codetype().lift())($arg_name.address)),
+            _ => quote!($(arg_type.as_codetype().lift())($arg_name)),
+        };
+        quote!(final arg$(arg_idx) = $lifted;)
     }
 
-    // Method to get argument name for a callback method based on type and index
-    pub fn callback_arg_name(arg_type: &Type, arg_idx: usize) -> dart::Tokens {
-        if let Type::Boolean = arg_type {
-            quote!(bool_arg$(arg_idx))
-        } else {
-            quote!(arg$(arg_idx))
-        }
+    // Method to get argument name for a callback method based on its index
+    pub fn callback_arg_name(arg_idx: usize) -> dart::Tokens {
+        quote!(arg$(arg_idx))
     }
 
     /// Lower argument with special handling for callback traits
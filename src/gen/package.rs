@@ -0,0 +1,101 @@
+use anyhow::Result;
+use genco::prelude::*;
+use uniffi_bindgen::{ComponentInterface, GenerationSettings};
+
+use super::types::{self, Runtime};
+use super::{generate_barrel_library, write_dart_file, Config, DartWrapper, OutputMode};
+
+/// Silences the lints the generated code trips on, as it follows the FFI symbol names
+const ANALYSIS_OPTIONS: &str = "analyzer:
+  errors:
+    camel_case_types: ignore
+    constant_identifier_names: ignore
+    non_constant_identifier_names: ignore
+    unused_element: ignore
+    unused_field: ignore
+";
+
+/// Declares the package a Flutter FFI plugin, for each platform `flutter` builds the crate on
+const FLUTTER_PLUGIN: &str = "
+flutter:
+  plugin:
+    platforms:
+      android:
+        ffiPlugin: true
+      ios:
+        ffiPlugin: true
+      linux:
+        ffiPlugin: true
+      macos:
+        ffiPlugin: true
+";
+
+fn pubspec(ci: &ComponentInterface, config: &Config, runtime: &Runtime) -> String {
+    let mut dependencies = vec![("ffi".to_string(), "^2.0.1".to_string())];
+    if let Runtime::Package(package) = runtime {
+        dependencies.push((package.clone(), format!("^{}", types::RUNTIME_VERSION)));
+    }
+    let plugin = config.output_mode() == OutputMode::FlutterPlugin;
+
+    format!(
+        "name: {name}
+description: Dart bindings for the {crate_name} Rust crate, generated by uniffi-dart
+version: {version}
+
+environment:
+  sdk: ^3.5.0
+{flutter_environment}
+dependencies:
+{dependencies}{flutter_dependency}{flutter_plugin}",
+        flutter_environment = if plugin { "  flutter: '>=3.3.0'\n" } else { "" },
+        flutter_dependency = if plugin {
+            "  flutter:\n    sdk: flutter\n"
+        } else {
+            ""
+        },
+        flutter_plugin = if plugin { FLUTTER_PLUGIN } else { "" },
+        name = config.dart_package_name(ci),
+        crate_name = ci.crate_name(),
+        version = config.package_version(),
+        dependencies = dependencies
+            .iter()
+            .map(|(name, version)| format!("  {name}: {version}\n"))
+            .collect::<String>(),
+    )
+}
+
+/// Write the package layout around the bindings already in `lib/src`: `pubspec.yaml`,
+/// `analysis_options.yaml` and the public library exporting their user-facing API
+pub(super) fn write_package(
+    settings: &GenerationSettings,
+    ci: &ComponentInterface,
+    config: &Config,
+    runtime: &Runtime,
+    wrappers: &[DartWrapper],
+) -> Result<()> {
+    let name = config.dart_package_name(ci);
+    std::fs::write(
+        settings.out_dir.join("pubspec.yaml"),
+        pubspec(ci, config, runtime),
+    )?;
+    std::fs::write(
+        settings.out_dir.join("analysis_options.yaml"),
+        ANALYSIS_OPTIONS,
+    )?;
+
+    // Several components need the barrel's initializers and hidden duplicates
+    let library = match wrappers {
+        [wrapper] => quote! {
+            library;
+
+            $(wrapper.public_exports("src/"))
+        },
+        _ => generate_barrel_library(&name, "src/", wrappers),
+    };
+    let lib_settings = GenerationSettings {
+        out_dir: settings.out_dir.join("lib"),
+        try_format_code: settings.try_format_code,
+        cdylib: settings.cdylib.clone(),
+    };
+    write_dart_file(&lib_settings, &name, library)
+}
@@ -27,7 +27,7 @@ impl Renderable for BooleanCodeType {
                 }
 
                 static RustBuffer lowerIntoRustBuffer( bool value) {
-                    return toRustBuffer(Uint8List.fromList([FfiConverterBool.lower(value)]));
+                    return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                 }
 
                 static int allocationSize([bool value = false]) {
@@ -13,13 +13,11 @@ impl Renderable for DurationCodeType {
         quote! {
             class FfiConverterDuration {
                 static Duration lift( RustBuffer buf) {
-                    return FfiConverterDuration.read(buf.asUint8List()).value;
+                    return buf.consume((bytes) => FfiConverterDuration.read(bytes).value);
                 }
 
                 static RustBuffer lower( Duration value) {
-                    final buf = Uint8List(allocationSize(value));
-                    write(value, buf);
-                    return toRustBuffer(buf);
+                    return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                 }
 
                 static LiftRetVal<Duration> read( Uint8List buf) {
@@ -35,19 +35,17 @@ macro_rules! impl_renderable_for_primitive {
                 quote! {
                     class $cl_name {
                         static $type_signature lift(RustBuffer value) {
-                            return $cl_name.read(value.asUint8List()).value;
+                            return value.consume((bytes) => $cl_name.read(bytes).value);
                         }
 
                         static LiftRetVal<$type_signature> read(Uint8List buf) {
                             final length = buf.buffer.asByteData(buf.offsetInBytes).getInt32(0);
-                            final bytes = Uint8List.view(buf.buffer, buf.offsetInBytes + 4, length);
-                            return LiftRetVal(bytes, length + 4);
+                            // Copied, as the buffer may be Rust memory that's freed once lifted
+                            return LiftRetVal(buf.sublist(4, length + 4), length + 4);
                         }
 
                         static RustBuffer lower($type_signature value) {
-                            final buf = Uint8List(allocationSize(value));
-                            write(value, buf);
-                            return toRustBuffer(buf);
+                            return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
                         }
 
                         static int allocationSize([$type_signature? value]) {
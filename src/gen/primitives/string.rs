@@ -20,11 +20,12 @@ impl Renderable for StringCodeType {
             class FfiConverterString {
                 static String lift( RustBuffer buf) {
                     // reading the entire buffer, the len is where the string finishes
-                    return utf8.decoder.convert(buf.asUint8List());
+                    return buf.consume((bytes) => utf8.decoder.convert(bytes));
                 }
 
                 static RustBuffer lower( String value) {
-                    return toRustBuffer(Utf8Encoder().convert(value));
+                    // A string of its own fills the buffer, without the length `write` prefixes
+                    return RustBuffer.allocWith(_utf8Length(value), (bytes) => _encodeInto(value, bytes));
                 }
 
                 static LiftRetVal<String> read( Uint8List buf) {
@@ -33,16 +34,63 @@ impl Renderable for StringCodeType {
                 }
 
                 static int allocationSize([String value = ""]) {
-                    // FIXME: doing this twice for every string is bad
-                    return utf8.encoder.convert(value).length + 4; // Four additional bytes for the length data
+                    return _utf8Length(value) + 4; // Four additional bytes for the length data
                 }
 
                 static int write( String value, Uint8List buf) {
-                    // two memcopies feels bad :(
-                    final list = utf8.encoder.convert(value);
-                    buf.buffer.asByteData(buf.offsetInBytes).setInt32(0, list.length);
-                    buf.setAll(4, list);
-                    return list.length + 4;
+                    final length = _encodeInto(value, Uint8List.view(buf.buffer, buf.offsetInBytes + 4));
+                    buf.buffer.asByteData(buf.offsetInBytes).setInt32(0, length);
+                    return length + 4;
+                }
+
+                // Bytes `value` takes in UTF-8, unpaired surrogates becoming U+FFFD like `utf8.encode` does
+                static int _utf8Length(String value) {
+                    var length = 0;
+                    for (var i = 0; i < value.length; i++) {
+                        final unit = value.codeUnitAt(i);
+                        if (unit < 0x80) {
+                            length += 1;
+                        } else if (unit < 0x800) {
+                            length += 2;
+                        } else if ((unit & 0xFC00) == 0xD800 && i + 1 < value.length && (value.codeUnitAt(i + 1) & 0xFC00) == 0xDC00) {
+                            length += 4;
+                            i++;
+                        } else {
+                            length += 3;
+                        }
+                    }
+                    return length;
+                }
+
+                // Encode `value` as UTF-8 straight into `buf`, which holds at least `_utf8Length(value)` bytes
+                static int _encodeInto(String value, Uint8List buf) {
+                    var offset = 0;
+                    for (var i = 0; i < value.length; i++) {
+                        var rune = value.codeUnitAt(i);
+                        if ((rune & 0xF800) == 0xD800) {
+                            if ((rune & 0xFC00) == 0xD800 && i + 1 < value.length && (value.codeUnitAt(i + 1) & 0xFC00) == 0xDC00) {
+                                rune = 0x10000 + ((rune & 0x3FF) << 10) + (value.codeUnitAt(++i) & 0x3FF);
+                            } else {
+                                rune = 0xFFFD;
+                            }
+                        }
+                        if (rune < 0x80) {
+                            buf[offset++] = rune;
+                        } else if (rune < 0x800) {
+                            buf[offset++] = 0xC0 | (rune >> 6);
+                            buf[offset++] = 0x80 | (rune & 0x3F);
+                        } else if (rune < 0x10000) {
+                            buf[offset++] = 0xE0 | (rune >> 12);
+                            buf[offset++] = 0x80 | ((rune >> 6) & 0x3F);
+                            buf[offset++] = 0x80 | (rune & 0x3F);
+                        } else {
+                            buf[offset++] = 0xF0 | (rune >> 18);
+                            buf[offset++] = 0x80 | ((rune >> 12) & 0x3F);
+                            buf[offset++] = 0x80 | ((rune >> 6) & 0x3F);
+                            buf[offset++] = 0x80 | (rune & 0x3F);
+                        }
+                    }
+                    return offset;
                 }
             }
         }
@@ -53,15 +53,19 @@ pub fn generate_record(obj: &Record, type_helper: &dyn TypeHelperRenderer) -> da
         type_helper.include_once_check(&f.as_codetype().canonical_name(), &f.as_type());
     }
     quote! {
+        $(DartCodeOracle::doc_comment(obj.docstring()))
         class $cls_name {
-            $(for f in obj.fields() => final $(generate_type(&f.as_type())) $(DartCodeOracle::var_name(f.name()));)
+            $(for f in obj.fields() {
+                $(DartCodeOracle::doc_comment(f.docstring()))
+                final $(generate_type(&f.as_type())) $(DartCodeOracle::var_name(f.name()));
+            })
 
             $(cls_name)($(for f in obj.fields() => this.$(DartCodeOracle::var_name(f.name())), ));
         }
 
         class $ffi_conv_name {
             static $cls_name lift( RustBuffer buf) {
-                return $ffi_conv_name.read(buf.asUint8List()).value;
+                return buf.consume((bytes) => $ffi_conv_name.read(bytes).value);
             }
 
             static LiftRetVal<$cls_name> read( Uint8List buf) {
@@ -78,10 +82,7 @@ pub fn generate_record(obj: &Record, type_helper: &dyn TypeHelperRenderer) -> da
             }
 
             static RustBuffer lower( $cls_name value) {
-                final total_length = $(for f in obj.fields() => $(f.as_type().as_codetype().ffi_converter_name()).allocationSize(value.$(DartCodeOracle::var_name(f.name()))) + ) 0;
-                final buf = Uint8List(total_length);
-                write(value, buf);
-                return toRustBuffer(buf);
+                return RustBuffer.allocWith(allocationSize(value), (bytes) => write(value, bytes));
             }
 
             static int write( $cls_name value, Uint8List buf) {
@@ -4,12 +4,15 @@ use genco::{lang::dart, quote};
 use uniffi_bindgen::interface::{AsType, Enum, Object, Record, Type};
 use uniffi_bindgen::ComponentInterface;
 
+use super::Config;
+
 pub trait Renderer<T> {
     fn render(&self) -> T;
 }
 
 pub trait TypeHelperRenderer {
     fn get_ci(&self) -> &ComponentInterface;
+    fn get_config(&self) -> &Config;
     fn include_once_check(&self, name: &str, ty: &Type) -> bool;
     fn check(&self, name: &str) -> bool;
 
@@ -0,0 +1,264 @@
+use anyhow::Result;
+use serde::Serialize;
+use uniffi_bindgen::interface::{Argument, AsType, Method, Type};
+use uniffi_bindgen::ComponentInterface;
+
+use super::check::unsupported_reason;
+use super::objects::dart_constructor_name;
+use super::oracle::{AsCodeType, DartCodeOracle};
+
+/// How a Rust type is mapped onto Dart
+#[derive(Debug, Serialize)]
+pub struct TypeRepr {
+    pub rust: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dart_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ffi_converter: Option<String>,
+    /// Set instead of the Dart mapping when the backend can't handle the type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsupported: Option<String>,
+}
+
+impl From<&Type> for TypeRepr {
+    fn from(ty: &Type) -> Self {
+        let rust = format!("{ty:?}");
+        match unsupported_reason(ty) {
+            Some(reason) => TypeRepr {
+                rust,
+                dart_type: None,
+                ffi_converter: None,
+                unsupported: Some(reason),
+            },
+            None => {
+                let code_type = ty.as_codetype();
+                TypeRepr {
+                    rust,
+                    dart_type: Some(code_type.type_label()),
+                    ffi_converter: Some(code_type.ffi_converter_name()),
+                    unsupported: None,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArgumentRepr {
+    pub name: String,
+    pub dart_name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeRepr,
+}
+
+impl From<&Argument> for ArgumentRepr {
+    fn from(arg: &Argument) -> Self {
+        ArgumentRepr {
+            name: arg.name().to_string(),
+            dart_name: DartCodeOracle::var_name(arg.name()),
+            type_: TypeRepr::from(&arg.as_type()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldRepr {
+    pub name: String,
+    pub dart_name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeRepr,
+}
+
+/// A function, constructor or method
+#[derive(Debug, Serialize)]
+pub struct CallableRepr {
+    pub name: String,
+    /// How Dart calls it, `ClassName` or `ClassName.name` for constructors
+    pub dart_name: String,
+    pub ffi_symbol: String,
+    pub is_async: bool,
+    pub arguments: Vec<ArgumentRepr>,
+    pub return_type: Option<TypeRepr>,
+    pub throws_type: Option<TypeRepr>,
+}
+
+impl CallableRepr {
+    fn from_method(method: &Method) -> Self {
+        CallableRepr {
+            name: method.name().to_string(),
+            dart_name: DartCodeOracle::fn_name(method.name()),
+            ffi_symbol: method.ffi_func().name().to_string(),
+            is_async: method.is_async(),
+            arguments: method.arguments().into_iter().map(Into::into).collect(),
+            return_type: method.return_type().map(Into::into),
+            throws_type: method.throws_type().map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ObjectRepr {
+    #[serde(flatten)]
+    pub type_: TypeRepr,
+    pub name: String,
+    pub ffi_clone_symbol: String,
+    pub ffi_free_symbol: String,
+    pub constructors: Vec<CallableRepr>,
+    pub methods: Vec<CallableRepr>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordRepr {
+    #[serde(flatten)]
+    pub type_: TypeRepr,
+    pub name: String,
+    pub fields: Vec<FieldRepr>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VariantRepr {
+    pub name: String,
+    pub dart_name: String,
+    pub fields: Vec<FieldRepr>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnumRepr {
+    #[serde(flatten)]
+    pub type_: TypeRepr,
+    pub name: String,
+    pub is_flat: bool,
+    pub variants: Vec<VariantRepr>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CallbackInterfaceRepr {
+    #[serde(flatten)]
+    pub type_: TypeRepr,
+    pub name: String,
+    pub ffi_init_symbol: String,
+    pub methods: Vec<CallableRepr>,
+}
+
+/// The component interface together with the Dart mapping of everything in it
+#[derive(Debug, Serialize)]
+pub struct ComponentRepr {
+    pub namespace: String,
+    pub crate_name: String,
+    pub functions: Vec<CallableRepr>,
+    pub objects: Vec<ObjectRepr>,
+    pub records: Vec<RecordRepr>,
+    pub enums: Vec<EnumRepr>,
+    pub callback_interfaces: Vec<CallbackInterfaceRepr>,
+}
+
+fn field_repr(name: &str, ty: &Type) -> FieldRepr {
+    FieldRepr {
+        name: name.to_string(),
+        dart_name: DartCodeOracle::var_name(name),
+        type_: TypeRepr::from(ty),
+    }
+}
+
+impl From<&ComponentInterface> for ComponentRepr {
+    fn from(ci: &ComponentInterface) -> Self {
+        ComponentRepr {
+            namespace: ci.namespace().to_string(),
+            crate_name: ci.crate_name().to_string(),
+            functions: ci
+                .function_definitions()
+                .iter()
+                .map(|func| CallableRepr {
+                    name: func.name().to_string(),
+                    dart_name: DartCodeOracle::fn_name(func.name()),
+                    ffi_symbol: func.ffi_func().name().to_string(),
+                    is_async: func.is_async(),
+                    arguments: func.arguments().into_iter().map(Into::into).collect(),
+                    return_type: func.return_type().map(Into::into),
+                    throws_type: func.throws_type().map(Into::into),
+                })
+                .collect(),
+            objects: ci
+                .object_definitions()
+                .iter()
+                .map(|obj| ObjectRepr {
+                    type_: TypeRepr::from(&obj.as_type()),
+                    name: obj.name().to_string(),
+                    ffi_clone_symbol: obj.ffi_object_clone().name().to_string(),
+                    ffi_free_symbol: obj.ffi_object_free().name().to_string(),
+                    constructors: obj
+                        .constructors()
+                        .into_iter()
+                        .map(|cons| CallableRepr {
+                            name: cons.name().to_string(),
+                            dart_name: dart_constructor_name(obj.name(), cons.name()),
+                            ffi_symbol: cons.ffi_func().name().to_string(),
+                            is_async: cons.is_async(),
+                            arguments: cons.arguments().into_iter().map(Into::into).collect(),
+                            return_type: None,
+                            throws_type: cons.throws_type().map(Into::into),
+                        })
+                        .collect(),
+                    methods: obj
+                        .methods()
+                        .into_iter()
+                        .map(CallableRepr::from_method)
+                        .collect(),
+                })
+                .collect(),
+            records: ci
+                .record_definitions()
+                .map(|rec| RecordRepr {
+                    type_: TypeRepr::from(&rec.as_type()),
+                    name: rec.name().to_string(),
+                    fields: rec
+                        .fields()
+                        .iter()
+                        .map(|f| field_repr(f.name(), &f.as_type()))
+                        .collect(),
+                })
+                .collect(),
+            enums: ci
+                .enum_definitions()
+                .map(|enum_| EnumRepr {
+                    type_: TypeRepr::from(&enum_.as_type()),
+                    name: enum_.name().to_string(),
+                    is_flat: enum_.is_flat(),
+                    variants: enum_
+                        .variants()
+                        .iter()
+                        .map(|variant| VariantRepr {
+                            name: variant.name().to_string(),
+                            dart_name: DartCodeOracle::enum_variant_name(variant.name()),
+                            fields: variant
+                                .fields()
+                                .iter()
+                                .map(|f| field_repr(f.name(), &f.as_type()))
+                                .collect(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            callback_interfaces: ci
+                .callback_interface_definitions()
+                .iter()
+                .map(|cbi| CallbackInterfaceRepr {
+                    type_: TypeRepr::from(&cbi.as_type()),
+                    name: cbi.name().to_string(),
+                    ffi_init_symbol: cbi.ffi_init_callback().name().to_string(),
+                    methods: cbi
+                        .methods()
+                        .into_iter()
+                        .map(CallableRepr::from_method)
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Render `components` and their Dart mapping as pretty-printed JSON
+pub fn print_repr(components: &[ComponentInterface]) -> Result<String> {
+    let repr: Vec<_> = components.iter().map(ComponentRepr::from).collect();
+    Ok(serde_json::to_string_pretty(&repr)?)
+}
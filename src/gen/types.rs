@@ -1,32 +1,126 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::{cell::RefCell, collections::HashMap};
 
 use genco::prelude::*;
-use uniffi_bindgen::interface::AsType;
+use uniffi_bindgen::interface::{AsType, FfiFunction};
 use uniffi_bindgen::{interface::Type, ComponentInterface};
 
 use super::render::{AsRenderable, Renderable, Renderer, TypeHelperRenderer};
 use super::{enums, functions, objects, oracle::AsCodeType, records};
 use crate::gen::oracle::DartCodeOracle;
+use crate::gen::Config;
 
 type FunctionDefinition = dart::Tokens;
 
+/// Name of the library holding the runtime, when it is not rendered into the bindings
+pub const SHARED_RUNTIME_LIBRARY: &str = "uniffi_runtime";
+
+/// Version of the runtime package, which bindings importing it check against
+pub const RUNTIME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Where the bindings get the runtime from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Runtime {
+    /// Rendered into the bindings
+    Inline,
+    /// Imported from [`SHARED_RUNTIME_LIBRARY`], generated next to the bindings of every
+    /// component of the cdylib
+    Shared,
+    /// Imported from the runtime package with the given name
+    Package(String),
+}
+
+impl Runtime {
+    fn import_uri(&self) -> Option<String> {
+        match self {
+            Runtime::Inline => None,
+            Runtime::Shared => Some(format!("{SHARED_RUNTIME_LIBRARY}.dart")),
+            Runtime::Package(package) => {
+                Some(format!("package:{package}/{SHARED_RUNTIME_LIBRARY}.dart"))
+            }
+        }
+    }
+
+    /// Whether the helpers of `ty` come with the runtime rather than the bindings
+    pub fn provides_helper(&self, ty: &Type) -> bool {
+        match self {
+            Runtime::Inline => false,
+            Runtime::Shared => is_runtime_type(ty),
+            // The package is generated independently of any interface, so it can only hold
+            // the helpers every interface may need
+            Runtime::Package(_) => BUILTIN_TYPES.contains(ty),
+        }
+    }
+}
+
+/// Types whose helpers the runtime package provides
+const BUILTIN_TYPES: [Type; 14] = [
+    Type::UInt8,
+    Type::Int8,
+    Type::UInt16,
+    Type::Int16,
+    Type::UInt32,
+    Type::Int32,
+    Type::UInt64,
+    Type::Int64,
+    Type::Float32,
+    Type::Float64,
+    Type::Boolean,
+    Type::String,
+    Type::Bytes,
+    Type::Duration,
+];
+
 pub struct TypeHelpersRenderer<'a> {
     ci: &'a ComponentInterface,
+    config: &'a Config,
     include_once_names: RefCell<HashMap<String, Type>>,
+    runtime: Runtime,
 }
 
 impl<'a> TypeHelpersRenderer<'a> {
-    pub fn new(ci: &'a ComponentInterface) -> Self {
+    /// Unless the `runtime` is inline, the runtime and the helpers it provides are imported
+    /// instead of being rendered
+    pub fn new(ci: &'a ComponentInterface, config: &'a Config, runtime: Runtime) -> Self {
         Self {
             ci,
+            config,
             include_once_names: RefCell::new(HashMap::new()),
+            runtime,
         }
     }
 
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
     pub fn get_include_names(&self) -> HashMap<String, Type> {
         self.include_once_names.clone().into_inner()
     }
+
+    /// Render the runtime library, with the helpers of `runtime_types`, or of the builtin types
+    /// when rendering the runtime package
+    pub fn render_runtime(&self, runtime_types: impl IntoIterator<Item = Type>) -> dart::Tokens {
+        let runtime_types: Vec<_> = match self.runtime {
+            Runtime::Package(_) => BUILTIN_TYPES.to_vec(),
+            _ => runtime_types.into_iter().collect(),
+        };
+        for ty in runtime_types {
+            self.include_once_check(&ty.as_codetype().canonical_name(), &ty);
+        }
+        // The runtime itself needs the string converter
+        self.include_once_check(&Type::String.as_codetype().canonical_name(), &Type::String);
+
+        quote! {
+            $(dart_imports())
+
+            $(generate_runtime(None))
+
+            $(for (_, ty) in self.get_include_names().iter() {
+                $(ty.as_renderable().render_type_helper(self))
+            })
+        }
+    }
 }
 
 impl TypeHelperRenderer for TypeHelpersRenderer<'_> {
@@ -57,6 +151,10 @@ impl TypeHelperRenderer for TypeHelpersRenderer<'_> {
         self.ci
     }
 
+    fn get_config(&self) -> &Config {
+        self.config
+    }
+
     fn get_record(&self, name: &str) -> Option<&uniffi_bindgen::interface::Record> {
         self.ci.get_record_definition(name)
     }
@@ -80,16 +178,34 @@ impl Renderer<(FunctionDefinition, dart::Tokens)> for TypeHelpersRenderer<'_> {
             .ci
             .iter_external_types()
             .map(|ty| {
-                self.ci
-                    .namespace_for_type(ty)
-                    .expect("external type should have module_path")
+                let module_path = ty
+                    .module_path()
+                    .expect("external type should have module_path");
+                let namespace = DartCodeOracle::namespace_for_module_path(module_path, self.ci);
+                let uri = self.config.external_import(
+                    DartCodeOracle::crate_name_for_module_path(module_path),
+                    &namespace,
+                );
+                (namespace, uri)
+            })
+            .collect::<BTreeMap<_, _>>();
+        // Dart types that custom types are mapped to may need their own imports
+        let custom_type_imports = self
+            .ci
+            .iter_local_types()
+            .filter_map(|ty| match ty {
+                Type::Custom { name, .. } => self.config.custom_type(name),
+                _ => None,
             })
+            .flat_map(|config| config.imports())
             .collect::<BTreeSet<_>>();
-        // The second import statement uses a library prefix, to distinguish conflicting identifiers e.g. RustBuffer vs. ext.RustBuffer
+        // External bindings are only reachable through their library prefix, to distinguish conflicting identifiers e.g. RustBuffer vs. ext.RustBuffer
         let imports: dart::Tokens = quote!(
-            $( for imp in modules_to_import {
-                $(format!("import \"{}.dart\"", imp));
-                $(format!("import \"{}.dart\"", imp)) as $imp;
+            $( for (namespace, uri) in modules_to_import {
+                import $(quoted(uri)) as $namespace;
+            })
+            $( for uri in custom_type_imports {
+                import $(quoted(uri));
             })
         );
 
@@ -117,329 +233,435 @@ impl Renderer<(FunctionDefinition, dart::Tokens)> for TypeHelpersRenderer<'_> {
 
         // Let's include the string converter
         self.include_once_check(&Type::String.as_codetype().canonical_name(), &Type::String);
+        // Helpers the runtime provides are rendered with it instead
+        let helper_types = self
+            .get_include_names()
+            .into_values()
+            .filter(|ty| !self.runtime.provides_helper(ty))
+            .collect::<Vec<_>>();
         let helpers_definitions = quote! {
-            $(for (_, ty) in self.get_include_names().iter() => $(ty.as_renderable().render_type_helper(self)) )
+            $(for ty in helper_types.iter() {
+                $(if self.ci.is_external(ty) {
+                    $(generate_external_type(ty, self.ci))
+                } else {
+                    $(ty.as_renderable().render_type_helper(self))
+                })
+            })
         };
 
+        let runtime_import = match self.runtime.import_uri() {
+            Some(uri) => quote!(
+                import $(quoted(&uri));
+                export $(quoted(&uri));
+            ),
+            None => quote!(),
+        };
         let types_helper_code = quote! {
-            import "dart:async";
-            import "dart:convert";
-            import "dart:ffi";
-            import "dart:io" show Platform, File, Directory;
-            import "dart:isolate";
-            import "dart:typed_data";
-            import "package:ffi/ffi.dart";
+            $(dart_imports())
             $(imports)
+            $runtime_import
 
             $(types_definitions)
 
+            $(if self.runtime == Runtime::Inline {
+                $(generate_runtime(Some(self.ci)))
+            })
 
-            class UniffiInternalError implements Exception {
-                static const int bufferOverflow = 0;
-                static const int incompleteData = 1;
-                static const int unexpectedOptionalTag = 2;
-                static const int unexpectedEnumCase = 3;
-                static const int unexpectedNullPointer = 4;
-                static const int unexpectedRustCallStatusCode = 5;
-                static const int unexpectedRustCallError = 6;
-                static const int unexpectedStaleHandle = 7;
-                static const int rustPanic = 8;
+            $(helpers_definitions)
+        };
 
-                final int errorCode;
-                final String? panicMessage;
+        (types_helper_code, function_definitions)
+    }
+}
 
-                const UniffiInternalError(this.errorCode, this.panicMessage);
+/// Dart SDK and `ffi` package imports used by the generated code
+fn dart_imports() -> dart::Tokens {
+    quote! {
+        import "dart:async";
+        import "dart:convert";
+        import "dart:ffi";
+        import "dart:io" show Platform, File, Directory;
+        import "dart:isolate";
+        import "dart:typed_data";
+        import "package:ffi/ffi.dart";
+    }
+}
 
-                static UniffiInternalError panicked(String message) {
-                return UniffiInternalError(rustPanic, message);
-                }
+/// Types whose helpers only depend on the runtime, so components can share them
+pub fn is_runtime_type(ty: &Type) -> bool {
+    match ty {
+        Type::Optional { inner_type } | Type::Sequence { inner_type } => {
+            is_runtime_type(inner_type)
+        }
+        Type::Map {
+            key_type,
+            value_type,
+        } => is_runtime_type(key_type) && is_runtime_type(value_type),
+        Type::Object { .. }
+        | Type::Record { .. }
+        | Type::Enum { .. }
+        | Type::CallbackInterface { .. }
+        | Type::Custom { .. } => false,
+        _ => true,
+    }
+}
 
-                @override
-                String toString() {
-                switch (errorCode) {
-                    case bufferOverflow:
-                    return "UniFfi::BufferOverflow";
-                    case incompleteData:
-                    return "UniFfi::IncompleteData";
-                    case unexpectedOptionalTag:
-                    return "UniFfi::UnexpectedOptionalTag";
-                    case unexpectedEnumCase:
-                    return "UniFfi::UnexpectedEnumCase";
-                    case unexpectedNullPointer:
-                    return "UniFfi::UnexpectedNullPointer";
-                    case unexpectedRustCallStatusCode:
-                    return "UniFfi::UnexpectedRustCallStatusCode";
-                    case unexpectedRustCallError:
-                    return "UniFfi::UnexpectedRustCallError";
-                    case unexpectedStaleHandle:
-                    return "UniFfi::UnexpectedStaleHandle";
-                    case rustPanic:
-                    return $[str](UniFfi::rustPanic: $panicMessage);
-                    default:
-                    return $[str](UniFfi::UnknownError: $errorCode);
-                }
-                }
+/// Error handling, `RustBuffer` and the other definitions every component needs, independent
+/// of the types in its interface.
+///
+/// Inlined into the bindings of `ci`, `RustBuffer`s are managed through its FFI functions.
+/// Otherwise the runtime stands alone, and the first component to load registers them.
+fn generate_runtime(ci: Option<&ComponentInterface>) -> dart::Tokens {
+    let rust_buffer_fn = |name: &str, ffi_func: fn(&ComponentInterface) -> FfiFunction| match ci {
+        Some(ci) => quote!($(DartCodeOracle::find_lib_instance()).$(ffi_func(ci).name())),
+        None => quote!(UniffiRustBufferApi.instance.$name),
+    };
+    let alloc = &rust_buffer_fn("alloc", ComponentInterface::ffi_rustbuffer_alloc);
+    let free = &rust_buffer_fn("free", ComponentInterface::ffi_rustbuffer_free);
+    let reserve = &rust_buffer_fn("reserve", ComponentInterface::ffi_rustbuffer_reserve);
+
+    quote! {
+        $(if ci.is_none() {
+            const String uniffiRuntimeVersion = $(quoted(RUNTIME_VERSION));
+
+            $(generate_rust_buffer_api())
+        })
+
+        class UniffiInternalError implements Exception {
+            static const int bufferOverflow = 0;
+            static const int incompleteData = 1;
+            static const int unexpectedOptionalTag = 2;
+            static const int unexpectedEnumCase = 3;
+            static const int unexpectedNullPointer = 4;
+            static const int unexpectedRustCallStatusCode = 5;
+            static const int unexpectedRustCallError = 6;
+            static const int unexpectedStaleHandle = 7;
+            static const int rustPanic = 8;
+
+            final int errorCode;
+            final String? panicMessage;
+
+            const UniffiInternalError(this.errorCode, this.panicMessage);
+
+            static UniffiInternalError panicked(String message) {
+            return UniffiInternalError(rustPanic, message);
             }
 
-            const int CALL_SUCCESS = 0;
-            const int CALL_ERROR = 1;
-            const int CALL_UNEXPECTED_ERROR = 2;
+            @override
+            String toString() {
+            switch (errorCode) {
+                case bufferOverflow:
+                return "UniFfi::BufferOverflow";
+                case incompleteData:
+                return "UniFfi::IncompleteData";
+                case unexpectedOptionalTag:
+                return "UniFfi::UnexpectedOptionalTag";
+                case unexpectedEnumCase:
+                return "UniFfi::UnexpectedEnumCase";
+                case unexpectedNullPointer:
+                return "UniFfi::UnexpectedNullPointer";
+                case unexpectedRustCallStatusCode:
+                return "UniFfi::UnexpectedRustCallStatusCode";
+                case unexpectedRustCallError:
+                return "UniFfi::UnexpectedRustCallError";
+                case unexpectedStaleHandle:
+                return "UniFfi::UnexpectedStaleHandle";
+                case rustPanic:
+                return $[str](UniFfi::rustPanic: $panicMessage);
+                default:
+                return $[str](UniFfi::UnknownError: $errorCode);
+            }
+            }
+        }
 
-            final class RustCallStatus extends Struct {
-                @Int8()
-                external int code;
+        const int CALL_SUCCESS = 0;
+        const int CALL_ERROR = 1;
+        const int CALL_UNEXPECTED_ERROR = 2;
 
-                external RustBuffer errorBuf;
+        final class RustCallStatus extends Struct {
+            @Int8()
+            external int code;
 
-                //Pointer<RustCallStatus> asPointer() => Pointer<RustCallStatus>.fromAddress(address);
-            }
+            external RustBuffer errorBuf;
 
-            void checkCallStatus(UniffiRustCallStatusErrorHandler errorHandler, Pointer<RustCallStatus> status) {
+            //Pointer<RustCallStatus> asPointer() => Pointer<RustCallStatus>.fromAddress(address);
+        }
 
-                if (status.ref.code == CALL_SUCCESS) {
-                return;
-                } else if (status.ref.code == CALL_ERROR) {
-                throw errorHandler.lift(status.ref.errorBuf);
-                } else if (status.ref.code == CALL_UNEXPECTED_ERROR) {
-                if (status.ref.errorBuf.len > 0) {
-                    throw UniffiInternalError.panicked(FfiConverterString.lift(status.ref.errorBuf));
-                } else {
-                    throw UniffiInternalError.panicked("Rust panic");
-                }
-                } else {
-                throw UniffiInternalError.panicked("Unexpected RustCallStatus code: ${status.ref.code}");
-                }
+        void checkCallStatus(UniffiRustCallStatusErrorHandler errorHandler, Pointer<RustCallStatus> status) {
+
+            if (status.ref.code == CALL_SUCCESS) {
+            return;
+            } else if (status.ref.code == CALL_ERROR) {
+            throw errorHandler.lift(status.ref.errorBuf);
+            } else if (status.ref.code == CALL_UNEXPECTED_ERROR) {
+            if (status.ref.errorBuf.len > 0) {
+                throw UniffiInternalError.panicked(FfiConverterString.lift(status.ref.errorBuf));
+            } else {
+                throw UniffiInternalError.panicked("Rust panic");
             }
-
-            T rustCall<T>(T Function(Pointer<RustCallStatus>) callback, [UniffiRustCallStatusErrorHandler? errorHandler]) {
-                final status = calloc<RustCallStatus>();
-                try {
-                    final result = callback(status);
-                    checkCallStatus(errorHandler ?? NullRustCallStatusErrorHandler(), status);
-                    return result;
-                } finally {
-                calloc.free(status);
-                }
+            } else {
+            throw UniffiInternalError.panicked("Unexpected RustCallStatus code: ${status.ref.code}");
             }
+        }
 
-            class NullRustCallStatusErrorHandler extends UniffiRustCallStatusErrorHandler {
-                @override
-                Exception lift(RustBuffer errorBuf) {
-                errorBuf.free();
-                return UniffiInternalError.panicked("Unexpected CALL_ERROR");
-                }
+        T rustCall<T>(T Function(Pointer<RustCallStatus>) callback, [UniffiRustCallStatusErrorHandler? errorHandler]) {
+            final status = calloc<RustCallStatus>();
+            try {
+                final result = callback(status);
+                checkCallStatus(errorHandler ?? NullRustCallStatusErrorHandler(), status);
+                return result;
+            } finally {
+            calloc.free(status);
             }
+        }
 
-            abstract class UniffiRustCallStatusErrorHandler {
-                Exception lift(RustBuffer errorBuf);
+        class NullRustCallStatusErrorHandler extends UniffiRustCallStatusErrorHandler {
+            @override
+            Exception lift(RustBuffer errorBuf) {
+            errorBuf.free();
+            return UniffiInternalError.panicked("Unexpected CALL_ERROR");
             }
+        }
 
-            final class RustBuffer extends Struct {
-                @Uint64()
-                external int capacity;
-
-                @Uint64()
-                external int len;
-
-                external Pointer<Uint8> data;
-
-                static RustBuffer alloc(int size) {
-                    return rustCall((status) => $(DartCodeOracle::find_lib_instance()).$(self.ci.ffi_rustbuffer_alloc().name())(size, status));
-                }
-
-                static RustBuffer fromBytes(ForeignBytes bytes) {
-                    return rustCall((status) => $(DartCodeOracle::find_lib_instance()).$(self.ci.ffi_rustbuffer_from_bytes().name())(bytes, status));
-                }
+        abstract class UniffiRustCallStatusErrorHandler {
+            Exception lift(RustBuffer errorBuf);
+        }
 
-                // static RustBuffer from(Pointer<Uint8> bytes, int len) {
-                //   final foreignBytes = ForeignBytes(len: len, data: bytes);
-                //   return rustCall((status) => _UniffiLib.instance.ffi_uniffi_futures_rustbuffer_from_bytes(foreignBytes));
-                // }
+        final class RustBuffer extends Struct {
+            @Uint64()
+            external int capacity;
 
-                void free() {
-                    rustCall((status) => $(DartCodeOracle::find_lib_instance()).$(self.ci.ffi_rustbuffer_free().name())(this, status));
-                }
+            @Uint64()
+            external int len;
 
-                RustBuffer reserve(int additionalCapacity) {
-                return rustCall((status) => $(DartCodeOracle::find_lib_instance()).$(self.ci.ffi_rustbuffer_reserve().name())(this, additionalCapacity, status));
-                }
+            external Pointer<Uint8> data;
 
-                Uint8List asUint8List() {
-                final dataList = data.asTypedList(len);
-                final byteData = ByteData.sublistView(dataList);
-                return Uint8List.view(byteData.buffer);
-                }
+            static RustBuffer alloc(int size) {
+                return rustCall((status) => $alloc(size, status));
+            }
 
-                @override
-                String toString() {
-                return "RustBuffer{capacity: $capacity, len: $len, data: $data}";
+            // Allocate `size` bytes in Rust and have `writer` fill them in place, rather than
+            // copying them over from Dart memory
+            static RustBuffer allocWith(int size, void Function(Uint8List bytes) writer) {
+                final buffer = alloc(size);
+                try {
+                    writer(buffer.data.asTypedList(size));
+                } catch (_) {
+                    buffer.free();
+                    rethrow;
                 }
+                return buffer;
             }
 
-            RustBuffer toRustBuffer(Uint8List data) {
-                final length = data.length;
-
-                final Pointer<Uint8> frameData = calloc<Uint8>(length); // Allocate a pointer large enough.
-                final pointerList = frameData.asTypedList(length); // Create a list that uses our pointer and copy in the data.
-                pointerList.setAll(0, data); // FIXME: can we remove this memcopy somehow?
-
-                final bytes = calloc<ForeignBytes>();
-                bytes.ref.len = length;
-                bytes.ref.data = frameData;
-                return RustBuffer.fromBytes(bytes.ref);
+            void free() {
+                rustCall((status) => $free(this, status));
             }
 
-            final class ForeignBytes extends Struct {
-                @Int32()
-                external int len;
-                external Pointer<Uint8> data;
-
-                //ForeignBytes({required this.len, required this.data});
-
-                // factory ForeignBytes.fromTypedData(Uint8List typedData) {
-                //   final data = calloc<Uint8>(typedData.length);
-                //   final dataList = data.asTypedList(typedData.length);
-                //   dataList.setAll(0, typedData);
-                //   return ForeignBytes(len: typedData.length, data: data);
-                // }
-
-                void free() {
-                calloc.free(data);
+            // Read a value Rust handed over from the buffer, then free it. `reader` must copy
+            // what it keeps, as the bytes are only valid until then.
+            T consume<T>(T Function(Uint8List bytes) reader) {
+                try {
+                    return reader(asUint8List());
+                } finally {
+                    free();
                 }
             }
 
-            class LiftRetVal<T> {
-                final T value;
-                final int bytesRead;
-                const LiftRetVal(this.value, this.bytesRead);
-
-                LiftRetVal<T> copyWithOffset(int offset) {
-                    return LiftRetVal(value, bytesRead + offset);
-                }
+            RustBuffer reserve(int additionalCapacity) {
+            return rustCall((status) => $reserve(this, additionalCapacity, status));
             }
 
-            abstract class FfiConverter<D, F> {
-                const FfiConverter();
+            Uint8List asUint8List() {
+            final dataList = data.asTypedList(len);
+            final byteData = ByteData.sublistView(dataList);
+            return Uint8List.view(byteData.buffer);
+            }
 
-                D lift(F value);
-                F lower(D value);
-                D read(ByteData buffer, int offset);
-                void write(D value, ByteData buffer, int offset);
-                int size(D value);
+            @override
+            String toString() {
+            return "RustBuffer{capacity: $capacity, len: $len, data: $data}";
             }
+        }
+
+        // Only there for the signature of the FFI function copying foreign bytes into a
+        // RustBuffer, which the bindings don't call as they write into Rust memory directly
+        final class ForeignBytes extends Struct {
+            @Int32()
+            external int len;
+            external Pointer<Uint8> data;
+        }
 
-            mixin FfiConverterPrimitive<T> on FfiConverter<T, T> {
-                @override
-                T lift(T value) => value;
+        class LiftRetVal<T> {
+            final T value;
+            final int bytesRead;
+            const LiftRetVal(this.value, this.bytesRead);
 
-                @override
-                T lower(T value) => value;
+            LiftRetVal<T> copyWithOffset(int offset) {
+                return LiftRetVal(value, bytesRead + offset);
             }
+        }
 
-            Uint8List createUint8ListFromInt(int value) {
-                int length = value.bitLength ~/ 8 + 1;
+        abstract class FfiConverter<D, F> {
+            const FfiConverter();
 
-                // Ensure the length is either 4 or 8
-                if (length != 4 && length != 8) {
-                length = (value < 0x100000000) ? 4 : 8;
-                }
+            D lift(F value);
+            F lower(D value);
+            D read(ByteData buffer, int offset);
+            void write(D value, ByteData buffer, int offset);
+            int size(D value);
+        }
 
-                Uint8List uint8List = Uint8List(length);
+        mixin FfiConverterPrimitive<T> on FfiConverter<T, T> {
+            @override
+            T lift(T value) => value;
 
-                for (int i = length - 1; i >= 0; i--) {
-                uint8List[i] = value & 0xFF;
-                value >>= 8;
-                }
+            @override
+            T lower(T value) => value;
+        }
 
-                return uint8List;
+        const int UNIFFI_RUST_FUTURE_POLL_READY = 0;
+        const int UNIFFI_RUST_FUTURE_POLL_MAYBE_READY = 1;
+
+        typedef UniffiRustFutureContinuationCallback = Void Function(Uint64, Int8);
+
+        Future<T> uniffiRustCallAsync<T, F>(
+            Pointer<Void> Function() rustFutureFunc,
+            void Function(Pointer<Void>, Pointer<NativeFunction<UniffiRustFutureContinuationCallback>>, Pointer<Void>) pollFunc,
+            F Function(Pointer<Void>, Pointer<RustCallStatus>) completeFunc,
+            void Function(Pointer<Void>) freeFunc,
+            T Function(F) liftFunc, [
+            UniffiRustCallStatusErrorHandler? errorHandler,
+        ]) async {
+            final rustFuture = rustFutureFunc();
+            final completer = Completer<int>();
+
+            late final NativeCallable<UniffiRustFutureContinuationCallback> callback;
+
+            void poll() {
+                pollFunc(
+                    rustFuture,
+                    callback.nativeFunction,
+                    Pointer<Void>.fromAddress(0),
+                );
             }
-
-            $(helpers_definitions)
-
-            const int UNIFFI_RUST_FUTURE_POLL_READY = 0;
-            const int UNIFFI_RUST_FUTURE_POLL_MAYBE_READY = 1;
-
-            typedef UniffiRustFutureContinuationCallback = Void Function(Uint64, Int8);
-
-            Future<T> uniffiRustCallAsync<T, F>(
-                Pointer<Void> Function() rustFutureFunc,
-                void Function(Pointer<Void>, Pointer<NativeFunction<UniffiRustFutureContinuationCallback>>, Pointer<Void>) pollFunc,
-                F Function(Pointer<Void>, Pointer<RustCallStatus>) completeFunc,
-                void Function(Pointer<Void>) freeFunc,
-                T Function(F) liftFunc, [
-                UniffiRustCallStatusErrorHandler? errorHandler,
-            ]) async {
-                final rustFuture = rustFutureFunc();
-                final completer = Completer<int>();
-
-                late final NativeCallable<UniffiRustFutureContinuationCallback> callback;
-
-                void poll() {
-                    pollFunc(
-                        rustFuture,
-                        callback.nativeFunction,
-                        Pointer<Void>.fromAddress(0),
-                    );
-                }
-                void onResponse(int _idx, int pollResult) {
-                    if (pollResult == UNIFFI_RUST_FUTURE_POLL_READY) {
-                        completer.complete(pollResult);
-                    } else {
-                        poll();
-                    }
+            void onResponse(int _idx, int pollResult) {
+                if (pollResult == UNIFFI_RUST_FUTURE_POLL_READY) {
+                    completer.complete(pollResult);
+                } else {
+                    poll();
                 }
-                callback = NativeCallable<UniffiRustFutureContinuationCallback>.listener(onResponse);
+            }
+            callback = NativeCallable<UniffiRustFutureContinuationCallback>.listener(onResponse);
 
-                try {
-                    poll();
-                    await completer.future;
-                    callback.close();
+            try {
+                poll();
+                await completer.future;
+                callback.close();
 
 
-                    final status = calloc<RustCallStatus>();
-                    try {
+                final status = calloc<RustCallStatus>();
+                try {
 
-                        final result = completeFunc(rustFuture, status);
+                    final result = completeFunc(rustFuture, status);
 
-                        return liftFunc(result);
-                    } finally {
-                        calloc.free(status);
-                    }
+                    return liftFunc(result);
                 } finally {
-                    freeFunc(rustFuture);
+                    calloc.free(status);
                 }
+            } finally {
+                freeFunc(rustFuture);
             }
+        }
 
-            class UniffiHandleMap<T> {
-                final Map<int, T> _map = {};
-                int _counter = 0;
+        class UniffiHandleMap<T> {
+            final Map<int, T> _map = {};
+            int _counter = 0;
 
-                int insert(T obj) {
-                final handle = _counter++;
-                _map[handle] = obj;
-                return handle;
-                }
+            int get length => _map.length;
 
-                T get(int handle) {
-                final obj = _map[handle];
-                if (obj == null) {
-                    throw UniffiInternalError(
-                        UniffiInternalError.unexpectedStaleHandle, "Handle not found");
-                }
-                return obj;
-                }
+            int insert(T obj) {
+            final handle = _counter++;
+            _map[handle] = obj;
+            return handle;
+            }
 
-                void remove(int handle) {
-                if (_map.remove(handle) == null) {
-                    throw UniffiInternalError(
-                        UniffiInternalError.unexpectedStaleHandle, "Handle not found");
-                }
+            T get(int handle) {
+            final obj = _map[handle];
+            if (obj == null) {
+                throw UniffiInternalError(
+                    UniffiInternalError.unexpectedStaleHandle, "Handle not found");
+            }
+            return obj;
+            }
+
+            void remove(int handle) {
+            if (_map.remove(handle) == null) {
+                throw UniffiInternalError(
+                    UniffiInternalError.unexpectedStaleHandle, "Handle not found");
+            }
+            }
+        }
+    }
+}
+
+/// Registry of the FFI functions managing `RustBuffer`s, for a runtime outside the bindings
+fn generate_rust_buffer_api() -> dart::Tokens {
+    quote! {
+        class UniffiRustBufferApi {
+            final RustBuffer Function(int, Pointer<RustCallStatus>) alloc;
+            final void Function(RustBuffer, Pointer<RustCallStatus>) free;
+            final RustBuffer Function(RustBuffer, int, Pointer<RustCallStatus>) reserve;
+            // Name of the native library the functions come from
+            final String library;
+
+            const UniffiRustBufferApi({
+                required this.library,
+                required this.alloc,
+                required this.free,
+                required this.reserve,
+            });
+
+            static UniffiRustBufferApi? _instance;
+
+            static UniffiRustBufferApi get instance {
+                final instance = _instance;
+                if (instance == null) {
+                    throw StateError("No UniFFI component is loaded, call initialize() first");
                 }
+                return instance;
             }
 
-        };
+            // Any component of the cdylib can manage the buffers of the others, so the first
+            // to load serves them all. Another library may use another allocator, and must not
+            // free buffers it didn't allocate, so its components can't share the runtime.
+            static void register(UniffiRustBufferApi api) {
+                final instance = _instance;
+                if (instance == null) {
+                    _instance = api;
+                } else if (instance.library != api.library) {
+                    throw StateError($("\"The UniFFI runtime serves the components of one native library, `${instance.library}`, not those of `${api.library}`\""));
+                }
+            }
+        }
+    }
+}
 
-        (types_helper_code, function_definitions)
+/// Aliases a type defined by another crate's bindings, so it can be used like a local one
+pub fn generate_external_type(ty: &Type, ci: &ComponentInterface) -> dart::Tokens {
+    let namespace = &DartCodeOracle::namespace_for_module_path(
+        ty.module_path()
+            .expect("external type should have module_path"),
+        ci,
+    );
+    let codetype = ty.as_codetype();
+    let type_label = &codetype.type_label();
+    let ffi_converter_name = &codetype.ffi_converter_name();
+
+    quote! {
+        typedef $type_label = $namespace.$type_label;
+        $(if ffi_converter_name != type_label {
+            typedef $ffi_converter_name = $namespace.$ffi_converter_name;
+        })
     }
 }
 
@@ -460,10 +682,15 @@ pub fn generate_type(ty: &Type) -> dart::Tokens {
         Type::Boolean => quote!(bool),
         Type::Optional { inner_type } => quote!($(generate_type(inner_type))?),
         Type::Sequence { inner_type } => quote!(List<$(generate_type(inner_type))>),
+        Type::Map {
+            key_type,
+            value_type,
+        } => quote!(Map<$(generate_type(key_type)), $(generate_type(value_type))>),
         Type::Enum { name, .. } => quote!($(DartCodeOracle::class_name(name))),
         Type::Duration => quote!(Duration),
         Type::Record { name, .. } => quote!($name),
         Type::Custom { name, .. } => quote!($name),
+        Type::CallbackInterface { name, .. } => quote!($(DartCodeOracle::class_name(name))),
         _ => todo!("Type::{:?}", ty),
     }
 }
@@ -16,14 +16,41 @@ pub struct CompileSource {
 }
 
 pub fn run_test(fixture: &str, udl_path: &str, config_path: Option<&str>) -> Result<()> {
+    run_test_impl(fixture, udl_path, config_path, "test", false)
+}
+
+/// Like [`run_test`], but runs the Dart tests of `test_dir` instead of `test`, so one fixture
+/// can be tested under several configurations.
+pub fn run_test_in(
+    fixture: &str,
+    udl_path: &str,
+    config_path: Option<&str>,
+    test_dir: &str,
+) -> Result<()> {
+    run_test_impl(fixture, udl_path, config_path, test_dir, false)
+}
+
+/// Like [`run_test`], but generates bindings for every crate in the fixture's cdylib.
+pub fn run_library_test(fixture: &str, udl_path: &str, config_path: Option<&str>) -> Result<()> {
+    run_test_impl(fixture, udl_path, config_path, "test", true)
+}
+
+fn run_test_impl(
+    fixture: &str,
+    udl_path: &str,
+    config_path: Option<&str>,
+    test_dir: &str,
+    library_mode: bool,
+) -> Result<()> {
     // Use .tmp_tests/ directory in project root for easier debugging
     // Navigate to project root (fixtures are 2 levels deep: fixtures/fixture_name/)
     let tmp_tests_dir = Utf8Path::new("../../.tmp_tests");
-    create_dir_all(&tmp_tests_dir)?;
+    create_dir_all(tmp_tests_dir)?;
 
-    let script_path = Utf8Path::new(".").canonicalize_utf8()?;
+    // The out dir is named after the test directory, which keeps the runs of one fixture apart
+    let script_path = Utf8Path::new(test_dir).canonicalize_utf8()?;
     let test_helper = UniFFITestHelper::new(fixture)?;
-    let out_dir = test_helper.create_out_dir(&tmp_tests_dir, &script_path)?;
+    let out_dir = test_helper.create_out_dir(tmp_tests_dir, &script_path)?;
 
     let udl_path = Utf8Path::new(".").canonicalize_utf8()?.join(udl_path);
     let config_path = if let Some(path) = config_path {
@@ -35,7 +62,7 @@ pub fn run_test(fixture: &str, udl_path: &str, config_path: Option<&str>) -> Res
     println!("{out_dir}");
 
     let mut pubspec = File::create(out_dir.join("pubspec.yaml"))?;
-    pubspec.write(
+    pubspec.write_all(
         b"
     name: uniffi_test
     description: testing module for uniffi
@@ -53,17 +80,20 @@ pub fn run_test(fixture: &str, udl_path: &str, config_path: Option<&str>) -> Res
     let test_outdir = out_dir.join("test");
     create_dir_all(&test_outdir)?;
 
-    test_helper.copy_cdylib_to_out_dir(&out_dir)?;
     gen::generate_dart_bindings(
         &udl_path,
         config_path.as_deref(),
         Some(&out_dir),
         &test_helper.cdylib_path()?,
-        false, // library_mode
+        library_mode,
     )?;
+    // Bindings with a build hook get the library from it as a code asset
+    if !out_dir.join("hook/build.dart").exists() {
+        test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    }
     // Copy fixture test files to output directory
-    let test_glob_pattern = "test/*.dart";
-    for file in glob::glob(test_glob_pattern)?.filter_map(Result::ok) {
+    let test_glob_pattern = format!("{test_dir}/*.dart");
+    for file in glob::glob(&test_glob_pattern)?.filter_map(Result::ok) {
         let filename = file
             .file_name()
             .expect("bad filename")
@@ -57,3 +57,59 @@ pub fn export_stream(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Exports the functions the Dart bindings free objects with, for crates whose scaffolding
+/// doesn't come from `uniffi_dart::generate_scaffolding`: call it next to
+/// `uniffi::setup_scaffolding!()`.
+///
+/// A `NativeFinalizer` calls its function with the finalized token alone, while object free
+/// functions also take the `RustCallStatus` they report a panicking `Drop` through. The token
+/// is a box holding the object pointer and its free function, which `finalize` calls with a
+/// status of its own.
+#[proc_macro]
+pub fn setup_scaffolding(_input: TokenStream) -> TokenStream {
+    // The bindings find the functions through the name of the crate defining the objects
+    let crate_name = std::env::var("CARGO_CRATE_NAME").expect("CARGO_CRATE_NAME is set by cargo");
+    let token = format_ident!("uniffi_dart_{}_finalizer_token", crate_name);
+    let finalize = format_ident!("uniffi_dart_{}_finalize", crate_name);
+    let token_free = format_ident!("uniffi_dart_{}_finalizer_token_free", crate_name);
+
+    let expanded = quote! {
+        #[doc(hidden)]
+        mod uniffi_dart_finalizer {
+            type FreeFn =
+                unsafe extern "C" fn(*const ::std::ffi::c_void, &mut ::uniffi::RustCallStatus);
+
+            struct Token {
+                ptr: *const ::std::ffi::c_void,
+                free: FreeFn,
+            }
+
+            #[no_mangle]
+            extern "C" fn #token(
+                ptr: *const ::std::ffi::c_void,
+                free: FreeFn,
+            ) -> *mut ::std::ffi::c_void {
+                ::std::boxed::Box::into_raw(::std::boxed::Box::new(Token { ptr, free })).cast()
+            }
+
+            #[no_mangle]
+            unsafe extern "C" fn #finalize(token: *mut ::std::ffi::c_void) {
+                let token = ::std::boxed::Box::from_raw(token.cast::<Token>());
+                let mut status = ::uniffi::RustCallStatus::default();
+                (token.free)(token.ptr, &mut status);
+                // Nobody is left to report a panicking `Drop` to
+                if status.code != ::uniffi::RustCallStatusCode::Success {
+                    ::std::mem::ManuallyDrop::into_inner(status.error_buf).destroy();
+                }
+            }
+
+            #[no_mangle]
+            unsafe extern "C" fn #token_free(token: *mut ::std::ffi::c_void) {
+                ::std::mem::drop(::std::boxed::Box::from_raw(token.cast::<Token>()));
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}